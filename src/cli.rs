@@ -0,0 +1,33 @@
+use std::{fs, process};
+
+use crate::{
+    log_checking::{analyze_log, checks::Severity},
+    log_upload::decode_log,
+};
+
+/// Prints the reports for a local log, so checks can be tested without running the bot
+pub(crate) fn analyze(path: &str) {
+    let log = match fs::read(path) {
+        Ok(log) => decode_log(log),
+        Err(err) => {
+            eprintln!("Failed to read {path}: {err}");
+            process::exit(1);
+        }
+    };
+
+    let reports = analyze_log(&log);
+    if reports.is_empty() {
+        println!("No issues found.");
+    }
+    for report in reports {
+        let color = match report.severity {
+            Severity::High => "31",
+            Severity::Medium => "33",
+            Severity::None => "34",
+        };
+        println!(
+            "\x1b[1;{color}m[{:?}] {}\x1b[0m\n{}\n",
+            report.severity, report.title, report.description
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use crate::log_upload::check_for_logs;
+use crate::{get_config, log_upload::check_for_logs};
 
 use super::{Context, Error};
 use poise::CreateReply;
@@ -11,9 +11,17 @@ use serenity::all::Message;
     interaction_context = "Guild|BotDm|PrivateChannel"
 )]
 pub async fn check_logs(ctx: Context<'_>, msg: Message) -> Result<(), Error> {
-    let reply = ctx.reply("Scanning for logs...").await?;
+    // Works on any message, so moderators can scan old messages or ones outside of scanned channels
+    let ephemeral = get_config!(ctx.serenity_context(), ctx.guild_id()).ephemeral_scans();
+    let reply = ctx
+        .send(
+            CreateReply::default()
+                .content("Scanning for logs...")
+                .ephemeral(ephemeral),
+        )
+        .await?;
 
-    match check_for_logs(ctx.serenity_context(), &msg, true).await {
+    match check_for_logs(ctx.serenity_context(), &msg, ctx.author().id, true).await {
         Ok(Some(edit)) => {
             let mut reply_builder = CreateReply::default().content(edit.0).components(edit.2);
             for ele in edit.1 {
@@ -0,0 +1,50 @@
+use poise::CreateReply;
+use serenity::builder::CreateEmbed;
+
+use crate::{
+    get_config,
+    log_checking::checks::{registered_checks, Severity},
+};
+
+use super::{Context, Error};
+
+/// List all issues the bot looks for in logs
+#[poise::command(
+    slash_command,
+    install_context = "Guild|User",
+    interaction_context = "Guild|BotDm|PrivateChannel"
+)]
+pub(crate) async fn checks(ctx: Context<'_>) -> Result<(), Error> {
+    let colors = get_config!(ctx.serenity_context(), ctx.guild_id()).colors();
+    let dynamic_checks = get_config!(ctx.serenity_context()).dynamic_checks.clone();
+    let mut descriptions = vec![String::new()];
+
+    for check in registered_checks(&dynamic_checks) {
+        let line = format!(
+            "- **{}** (`{}`, {:?}): {}\n",
+            check.title, check.id, check.severity, check.summary
+        );
+        let current = descriptions.last_mut().expect("No description?");
+        if current.len() + line.len() > 4000 {
+            descriptions.push(line);
+        } else {
+            current.push_str(&line);
+        }
+    }
+
+    // Discord limits the total embed size per message, so each page is sent separately
+    for (i, description) in descriptions.into_iter().enumerate() {
+        let mut embed = CreateEmbed::new()
+            .description(description)
+            .color(colors.get(Severity::None));
+        if i == 0 {
+            embed = embed.title(format!(
+                "Log checks ({})",
+                registered_checks(&dynamic_checks).count()
+            ));
+        }
+        ctx.send(CreateReply::default().embed(embed)).await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use serde::Serialize;
+use serenity::all::{ChannelId, Timestamp, UserId};
+use tokio::{fs::OpenOptions, io::AsyncWriteExt};
+
+use crate::{get_config, log_checking::checks::registered_checks, ConfigData};
+
+use super::{Context, Error};
+
+const DEFAULT_FALSE_POSITIVE_FILE: &str = "false_positives.jsonl";
+
+#[derive(Serialize)]
+struct FalsePositiveReport {
+    log: String,
+    check: String,
+    note: Option<String>,
+    reporter: UserId,
+    timestamp: Timestamp,
+}
+
+async fn autocomplete_check<'a>(
+    ctx: Context<'_>,
+    partial: &'a str,
+) -> impl Iterator<Item = &'static str> + 'a {
+    let ids: Vec<_> = registered_checks(&get_config!(ctx.serenity_context()).dynamic_checks)
+        .map(|check| check.id)
+        .collect();
+    ids.into_iter().filter(move |id| id.contains(partial))
+}
+
+/// Report a check that fired on a log where it shouldn't have
+#[poise::command(
+    slash_command,
+    rename = "falsepositive",
+    default_member_permissions = "MANAGE_MESSAGES",
+    install_context = "Guild",
+    interaction_context = "Guild"
+)]
+pub(crate) async fn false_positive(
+    ctx: Context<'_>,
+    #[description = "The URL or id of the log"] log: String,
+    #[description = "The id of the check that misfired"]
+    #[autocomplete = "autocomplete_check"]
+    check: String,
+    #[description = "Why the check is wrong"] note: Option<String>,
+) -> Result<(), Error> {
+    let (known, file, channel) = {
+        let data = ctx.serenity_context().data.read().await;
+        let config = data.get::<ConfigData>().expect("No config?");
+        (
+            registered_checks(&config.dynamic_checks).any(|c| c.id == check),
+            config
+                .false_positive_file
+                .clone()
+                .unwrap_or_else(|| DEFAULT_FALSE_POSITIVE_FILE.to_string()),
+            config.false_positive_channel,
+        )
+    };
+    if !known {
+        ctx.reply(format!("There is no check called `{check}`."))
+            .await?;
+        return Ok(());
+    }
+
+    let report = FalsePositiveReport {
+        log,
+        check,
+        note,
+        reporter: ctx.author().id,
+        timestamp: Timestamp::now(),
+    };
+    let line = serde_json::to_string(&report)?;
+
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(file)
+        .await?
+        .write_all(format!("{line}\n").as_bytes())
+        .await?;
+
+    if let Some(id) = channel {
+        ChannelId::new(id)
+            .say(ctx.http(), format!("```json\n{line}\n```"))
+            .await?;
+    }
+
+    ctx.reply(format!(
+        "Thanks, the false positive of `{}` was reported.",
+        report.check
+    ))
+    .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_is_one_json_line() {
+        let report = FalsePositiveReport {
+            log: "https://mclo.gs/abc".to_string(),
+            check: "exit_code".to_string(),
+            note: Some("The game\nwas closed normally".to_string()),
+            reporter: UserId::new(1),
+            timestamp: Timestamp::from_unix_timestamp(0).unwrap(),
+        };
+        let line = serde_json::to_string(&report).unwrap();
+        assert!(!line.contains('\n'));
+
+        let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(value["check"], "exit_code");
+        assert_eq!(value["note"], "The game\nwas closed normally");
+        assert_eq!(value["timestamp"], "1970-01-01T00:00:00Z");
+    }
+}
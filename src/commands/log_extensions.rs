@@ -0,0 +1,93 @@
+use crate::{
+    config::{config_toml, save_config, CONFIG_PATH},
+    ConfigData,
+};
+
+use super::{Context, Error};
+
+/// Scan attachments with this file extension for logs in every guild, for bot owners only
+#[poise::command(
+    slash_command,
+    rename = "addlogext",
+    default_member_permissions = "MANAGE_GUILD",
+    owners_only,
+    install_context = "Guild",
+    interaction_context = "Guild"
+)]
+pub(crate) async fn add_log_extension(
+    ctx: Context<'_>,
+    #[description = "The extension, such as .log"] extension: String,
+    #[description = "Also save the change to the config file"] save: Option<bool>,
+) -> Result<(), Error> {
+    let (added, toml) = {
+        let mut data = ctx.serenity_context().data.write().await;
+        let config = data.get_mut::<ConfigData>().expect("No config?");
+        let added = !config.log_extensions.contains(&extension);
+        if added {
+            config.log_extensions.push(extension.clone());
+        }
+        // Serialized while locked, but only written after the lock is released
+        let toml = save
+            .unwrap_or_default()
+            .then(|| config_toml(config))
+            .transpose()?;
+        (added, toml)
+    };
+
+    if !added {
+        ctx.reply(format!("`{extension}` is already scanned."))
+            .await?;
+        return Ok(());
+    }
+    if let Some(toml) = toml {
+        save_config(toml, CONFIG_PATH).await?;
+    }
+    ctx.reply(format!(
+        "Attachments ending with `{extension}` are now scanned."
+    ))
+    .await?;
+    Ok(())
+}
+
+/// Stop scanning attachments with this file extension in every guild, for bot owners only
+#[poise::command(
+    slash_command,
+    rename = "removelogext",
+    default_member_permissions = "MANAGE_GUILD",
+    owners_only,
+    install_context = "Guild",
+    interaction_context = "Guild"
+)]
+pub(crate) async fn remove_log_extension(
+    ctx: Context<'_>,
+    #[description = "The extension, such as .log"] extension: String,
+    #[description = "Also save the change to the config file"] save: Option<bool>,
+) -> Result<(), Error> {
+    let (removed, toml) = {
+        let mut data = ctx.serenity_context().data.write().await;
+        let config = data.get_mut::<ConfigData>().expect("No config?");
+        let len = config.log_extensions.len();
+        config
+            .log_extensions
+            .retain(|existing| existing != &extension);
+        // Serialized while locked, but only written after the lock is released
+        let toml = save
+            .unwrap_or_default()
+            .then(|| config_toml(config))
+            .transpose()?;
+        (config.log_extensions.len() != len, toml)
+    };
+
+    if !removed {
+        ctx.reply(format!("`{extension}` isn't scanned.")).await?;
+        return Ok(());
+    }
+    if let Some(toml) = toml {
+        save_config(toml, CONFIG_PATH).await?;
+    }
+    ctx.reply(format!(
+        "Attachments ending with `{extension}` are no longer scanned."
+    ))
+    .await?;
+    Ok(())
+}
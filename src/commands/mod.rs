@@ -6,6 +6,11 @@ pub mod tags;
 pub mod version;
 pub mod check_logs;
 pub mod modversion;
+pub mod scan;
+pub mod checks;
+pub mod stats;
+pub mod false_positive;
+pub mod log_extensions;
 
 pub type Error = Box<dyn std::error::Error + Send + Sync>;
 pub type Context<'a> = poise::Context<'a, ConfigData, Error>;
@@ -2,6 +2,8 @@ use poise::CreateReply;
 use reqwest::StatusCode;
 use serde::Deserialize;
 
+use crate::http::HTTP_CLIENT;
+
 use super::{Context, Error};
 
 #[derive(Deserialize)]
@@ -35,15 +37,17 @@ pub(crate) async fn modversion(
         None => String::new(),
     };
 
-    match reqwest::get(format!(
-        "{}/project/{}/version?{}loaders=[%22{}%22]",
-        crate::constants::MODRINTH_API_URL,
-        slug,
-        version_query,
-        loader
-    ))
-    .await?
-    .error_for_status()
+    match HTTP_CLIENT
+        .get(format!(
+            "{}/project/{}/version?{}loaders=[%22{}%22]",
+            crate::constants::MODRINTH_API_URL,
+            slug,
+            version_query,
+            loader
+        ))
+        .send()
+        .await?
+        .error_for_status()
     {
         Ok(modrinth_response) => {
             let mod_versions: Vec<ModVersion> =
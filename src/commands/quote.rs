@@ -1,14 +1,20 @@
 use std::path::Path;
 
-use crate::get_config;
+use crate::{
+    get_config,
+    quotes::{get_quotes, Quote},
+};
 
 use super::{Context, Error};
+use poise::CreateReply;
 use serenity::all::{Attachment, Message};
 use serenity::{
     all::ChannelId,
-    builder::{CreateEmbed, CreateEmbedAuthor, CreateMessage},
+    builder::{CreateEmbed, CreateEmbedAuthor, CreateEmbedFooter, CreateMessage},
 };
 
+const SEARCH_PAGE_SIZE: u64 = 10;
+
 fn is_image(filename: &str) -> bool {
     Path::new(filename).extension().map_or(false, |ext| {
         ext.eq_ignore_ascii_case("png")
@@ -25,15 +31,17 @@ async fn quote_internal(
     icon_url: Option<&String>,
     attachments: Option<&Vec<Attachment>>,
 ) -> Result<(), Error> {
-    match get_config!(ctx.serenity_context()).quotes_channel {
+    // Read first, so the config isn't locked while quoting
+    let quotes_channel = get_config!(ctx.serenity_context()).quotes_channel;
+    match quotes_channel {
         Some(id) => {
             let channel = ChannelId::new(id);
 
-            if ctx.guild_id().is_none() {
+            let Some(guild) = ctx.guild_id() else {
                 ctx.reply("Nuh uh :brombeere:").await?;
 
                 return Ok(());
-            }
+            };
 
             let mut embed_author = CreateEmbedAuthor::new(author);
 
@@ -43,18 +51,23 @@ async fn quote_internal(
 
             let mut embed = CreateEmbed::new().description(quote).author(embed_author);
 
-            if let Some(attachments) = attachments
-                && !attachments.is_empty()
-                && is_image(&attachments[0].filename)
-            {
-                embed = embed.image(&attachments[0].url);
+            let image = attachments
+                .and_then(|attachments| attachments.first())
+                .filter(|attachment| is_image(&attachment.filename))
+                .map(|attachment| attachment.url.as_str());
+            if let Some(image) = image {
+                embed = embed.image(image);
             }
 
             let builder = CreateMessage::new().embed(embed);
 
             match channel.send_message(ctx.http(), builder).await {
                 Ok(_) => {
-                    ctx.reply(format!("Quoted: '{quote}' - {author}")).await?;
+                    let id = get_quotes(ctx.serenity_context())
+                        .await
+                        .add(guild, author, quote, image)?;
+                    ctx.reply(format!("Quoted #{id}: '{quote}' - {author}"))
+                        .await?;
                 }
                 Err(e) => {
                     ctx.reply(format!("Failed to quote, {e}")).await?;
@@ -92,3 +105,116 @@ pub async fn context_quote(ctx: Context<'_>, msg: Message) -> Result<(), Error>
     )
     .await
 }
+
+/// Show a stored quote
+#[poise::command(slash_command, rename = "showquote", guild_only)]
+pub async fn show_quote(
+    ctx: Context<'_>,
+    #[description = "The number of the quote"] id: i64,
+) -> Result<(), Error> {
+    let guild = ctx.guild_id().expect("Guild only");
+    let Some(quote) = get_quotes(ctx.serenity_context()).await.get(guild, id)? else {
+        ctx.reply(format!("There is no quote #{id}.")).await?;
+        return Ok(());
+    };
+
+    ctx.send(CreateReply::default().embed(quote_embed(&quote)))
+        .await?;
+    Ok(())
+}
+
+/// Show a random stored quote
+#[poise::command(slash_command, rename = "randomquote", guild_only)]
+pub async fn random_quote(ctx: Context<'_>) -> Result<(), Error> {
+    let guild = ctx.guild_id().expect("Guild only");
+    let Some(quote) = get_quotes(ctx.serenity_context()).await.random(guild)? else {
+        ctx.reply("No quotes have been saved yet, quote something first!")
+            .await?;
+        return Ok(());
+    };
+
+    ctx.send(CreateReply::default().embed(quote_embed(&quote)))
+        .await?;
+    Ok(())
+}
+
+/// Search the stored quotes
+#[poise::command(slash_command, rename = "searchquotes", guild_only)]
+pub async fn search_quotes(
+    ctx: Context<'_>,
+    #[description = "The text to look for in quotes and their authors"] text: String,
+    #[description = "The page of results"]
+    #[min = 1]
+    page: Option<u64>,
+) -> Result<(), Error> {
+    let guild = ctx.guild_id().expect("Guild only");
+    let page = page.unwrap_or(1).max(1);
+    let (quotes, total) = get_quotes(ctx.serenity_context()).await.search(
+        guild,
+        &text,
+        SEARCH_PAGE_SIZE,
+        (page - 1) * SEARCH_PAGE_SIZE,
+    )?;
+
+    if total == 0 {
+        ctx.reply(format!("No quotes contain `{text}`.")).await?;
+        return Ok(());
+    }
+    let pages = total.div_ceil(SEARCH_PAGE_SIZE);
+    if quotes.is_empty() {
+        ctx.reply(format!("There are only {pages} pages of results."))
+            .await?;
+        return Ok(());
+    }
+
+    let description = quotes
+        .iter()
+        .map(|quote| {
+            let mut content: String = quote.content.chars().take(200).collect();
+            if content.len() < quote.content.len() {
+                content.push('…');
+            }
+            format!("**#{}**: {content} - {}", quote.id, quote.author)
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let embed = CreateEmbed::new()
+        .title(format!("Quotes containing \"{text}\""))
+        .description(description)
+        .footer(CreateEmbedFooter::new(format!(
+            "Page {page} of {pages}, {total} matches"
+        )));
+    ctx.send(CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Delete a stored quote
+#[poise::command(
+    slash_command,
+    rename = "deletequote",
+    default_member_permissions = "MANAGE_MESSAGES",
+    guild_only
+)]
+pub async fn delete_quote(
+    ctx: Context<'_>,
+    #[description = "The number of the quote"] id: i64,
+) -> Result<(), Error> {
+    let guild = ctx.guild_id().expect("Guild only");
+    if get_quotes(ctx.serenity_context()).await.delete(guild, id)? {
+        ctx.reply(format!("Deleted quote #{id}.")).await?;
+    } else {
+        ctx.reply(format!("There is no quote #{id}.")).await?;
+    }
+    Ok(())
+}
+
+fn quote_embed(quote: &Quote) -> CreateEmbed {
+    let mut embed = CreateEmbed::new()
+        .description(&quote.content)
+        .author(CreateEmbedAuthor::new(&quote.author))
+        .footer(CreateEmbedFooter::new(format!("#{}", quote.id)));
+    if let Some(image) = &quote.image {
+        embed = embed.image(image);
+    }
+    embed
+}
@@ -0,0 +1,45 @@
+use crate::log_upload::check_for_logs_at_url;
+
+use super::{Context, Error};
+use poise::CreateReply;
+
+/// Scan a log uploaded to mclo.gs or another supported paste service
+#[poise::command(
+    slash_command,
+    install_context = "Guild|User",
+    interaction_context = "Guild|BotDm|PrivateChannel"
+)]
+pub(crate) async fn scan(
+    ctx: Context<'_>,
+    #[description = "The URL of the uploaded log"] url: String,
+) -> Result<(), Error> {
+    let reply = ctx.reply("Scanning log...").await?;
+
+    match check_for_logs_at_url(ctx.serenity_context(), &url, ctx.guild_id()).await {
+        Ok(Some(edit)) => {
+            let mut reply_builder = CreateReply::default().content(edit.0).components(edit.2);
+            for ele in edit.1 {
+                reply_builder = reply_builder.embed(ele);
+            }
+
+            reply.edit(ctx, reply_builder).await?;
+        }
+        Ok(None) => {
+            reply
+                .edit(
+                    ctx,
+                    CreateReply::default().content("No log found at that URL."),
+                )
+                .await?;
+        }
+        Err(err) => {
+            reply
+                .edit(
+                    ctx,
+                    CreateReply::default().content(format!("Error, cannot scan log: {err}")),
+                )
+                .await?;
+        }
+    };
+    Ok(())
+}
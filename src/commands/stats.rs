@@ -0,0 +1,51 @@
+use std::sync::atomic::Ordering;
+
+use poise::CreateReply;
+use serenity::builder::CreateEmbed;
+
+use crate::{get_config, log_checking::checks::Severity, stats::get_stats};
+
+use super::{Context, Error};
+
+/// Show how many logs were scanned and which checks triggered since the bot started
+#[poise::command(
+    slash_command,
+    install_context = "Guild|User",
+    interaction_context = "Guild|BotDm|PrivateChannel"
+)]
+pub(crate) async fn stats(ctx: Context<'_>) -> Result<(), Error> {
+    let stats = get_stats(ctx.serenity_context()).await;
+    let colors = get_config!(ctx.serenity_context(), ctx.guild_id()).colors();
+
+    let mut checks: Vec<_> = stats
+        .checks_triggered
+        .iter()
+        .map(|(id, count)| (*id, count.load(Ordering::Relaxed)))
+        .filter(|(_, count)| *count > 0)
+        .collect();
+    checks.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+    let mut description = format!(
+        "**Logs scanned:** {}\n**Upload failures:** {}\n",
+        stats.logs_scanned.load(Ordering::Relaxed),
+        stats.upload_failures.load(Ordering::Relaxed)
+    );
+    if !checks.is_empty() {
+        description.push_str("\n**Checks triggered:**\n");
+        for (id, count) in checks {
+            description.push_str(&format!("- `{id}`: {count}\n"));
+        }
+    }
+
+    ctx.send(
+        CreateReply::default().embed(
+            CreateEmbed::new()
+                .title("Stats")
+                .description(description)
+                .color(colors.get(Severity::None)),
+        ),
+    )
+    .await?;
+
+    Ok(())
+}
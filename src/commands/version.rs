@@ -1,6 +1,10 @@
+use std::time::Duration;
+
 use poise::CreateReply;
 use serde::Deserialize;
 
+use crate::{http::HTTP_CLIENT, StartTime};
+
 use super::{Context, Error};
 
 #[derive(Deserialize)]
@@ -20,7 +24,7 @@ struct LoaderVersion {
     version: Option<String>,
 }
 
-/// Get current Fabric versions for a given Minecraft version
+/// Get current Fabric versions for a given Minecraft version, or the bot's own version
 #[poise::command(
     slash_command,
     install_context = "Guild|User",
@@ -28,16 +32,65 @@ struct LoaderVersion {
 )]
 pub(crate) async fn version(
     ctx: Context<'_>,
-    #[description = "Get the latest Fabric versions for the given game version"] version: String,
+    #[description = "Get the latest Fabric versions for the given game version"]
+    version: Option<String>,
 ) -> Result<(), Error> {
-    let mut yarn_response: &str = &reqwest::get(format!(
-        "{}/versions/yarn/{}?limit=1",
-        crate::constants::FABRIC_META_URL,
-        version
-    ))
-    .await?
-    .text()
+    match version {
+        Some(version) => fabric_versions(ctx, version).await,
+        None => bot_version(ctx).await,
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (days, hours, minutes, seconds) =
+        (secs / 86_400, secs / 3_600 % 24, secs / 60 % 60, secs % 60);
+
+    if days > 0 {
+        format!("{days}d {hours}h {minutes}m {seconds}s")
+    } else if hours > 0 {
+        format!("{hours}h {minutes}m {seconds}s")
+    } else if minutes > 0 {
+        format!("{minutes}m {seconds}s")
+    } else {
+        format!("{seconds}s")
+    }
+}
+
+async fn bot_version(ctx: Context<'_>) -> Result<(), Error> {
+    let uptime = ctx
+        .serenity_context()
+        .data
+        .read()
+        .await
+        .get::<StartTime>()
+        .map(|start| format_duration(start.elapsed()));
+
+    ctx.send(CreateReply::default().content(format!(
+        "```
+version={}
+commit={}
+uptime={}```",
+        env!("CARGO_PKG_VERSION"),
+        env!("GIT_COMMIT"),
+        uptime.unwrap_or_else(|| "unknown".to_owned())
+    )))
     .await?;
+
+    Ok(())
+}
+
+async fn fabric_versions(ctx: Context<'_>, version: String) -> Result<(), Error> {
+    let mut yarn_response: &str = &HTTP_CLIENT
+        .get(format!(
+            "{}/versions/yarn/{}?limit=1",
+            crate::constants::FABRIC_META_URL,
+            version
+        ))
+        .send()
+        .await?
+        .text()
+        .await?;
     if let Some(stripped) = yarn_response.strip_prefix('[') {
         yarn_response = stripped;
     }
@@ -49,14 +102,16 @@ pub(crate) async fn version(
     }
     let yarn_version: YarnVersion = serde_json::from_str(yarn_response)?;
 
-    let mut loader_response: &str = &reqwest::get(format!(
-        "{}/versions/loader/{}?limit=1",
-        crate::constants::FABRIC_META_URL,
-        version
-    ))
-    .await?
-    .text()
-    .await?;
+    let mut loader_response: &str = &HTTP_CLIENT
+        .get(format!(
+            "{}/versions/loader/{}?limit=1",
+            crate::constants::FABRIC_META_URL,
+            version
+        ))
+        .send()
+        .await?
+        .text()
+        .await?;
     if let Some(stripped) = loader_response.strip_prefix('[') {
         loader_response = stripped;
     }
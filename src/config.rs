@@ -1,8 +1,276 @@
+use std::{collections::HashMap, fs, io, path::Path, sync::Arc};
+
 use serde::{Deserialize, Serialize};
+use serenity::all::{ChannelId, GuildId};
+
+use crate::log_checking::{checks::Severity, dynamic::DynamicCheck};
+
+pub const CONFIG_PATH: &str = "config.toml";
+pub const GUILD_CONFIG_PATH: &str = "guilds.json";
+/// Shown when the config can't be loaded, everything but the token is optional
+pub const EXAMPLE_CONFIG: &str = r#"token = "your bot token"
+"#;
 
-#[derive(Serialize, Deserialize, Debug)]
+fn default_log_extensions() -> Vec<String> {
+    [".log", ".log.gz", ".zip"].map(String::from).to_vec()
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Config {
     pub token: String,
     pub quotes_channel: Option<u64>,
-    pub log_extensions: Option<Vec<String>>,
+    /// Path of the SQLite database quotes are stored in
+    pub quotes_db: Option<String>,
+    /// Zips are only opened when `.zip` is listed, `.txt` crash reports are scanned either way
+    #[serde(default = "default_log_extensions")]
+    pub log_extensions: Vec<String>,
+    #[serde(default)]
+    pub paste_services: Vec<String>,
+    #[serde(default)]
+    pub disabled_checks: Vec<String>,
+    #[serde(alias = "max_log_size")]
+    pub max_log_bytes: Option<u64>,
+    pub truncate_to: Option<usize>,
+    pub scan_channels: Option<Vec<ChannelId>>,
+    #[serde(default)]
+    pub ephemeral_scans: bool,
+    /// Posts scan results in a thread started from the message instead of replying to it
+    #[serde(default)]
+    pub reply_in_thread: bool,
+    pub health_port: Option<u16>,
+    #[serde(default)]
+    pub colors: Colors,
+    /// Seconds during which the same message isn't scanned again
+    pub scan_cooldown: Option<u64>,
+    /// How many log files a user can upload per minute
+    pub uploads_per_minute: Option<u32>,
+    pub false_positive_file: Option<String>,
+    pub false_positive_channel: Option<u64>,
+    pub large_modpack_threshold: Option<usize>,
+    /// Memory recommended per mod for large modpacks, in MiB
+    pub memory_per_mod_mb: Option<u64>,
+    /// Loaded from `GUILD_CONFIG_PATH` instead of the config file
+    #[serde(skip)]
+    pub guilds: HashMap<GuildId, GuildOverrides>,
+    /// Loaded from `DYNAMIC_CHECKS_PATH`
+    #[serde(skip)]
+    pub dynamic_checks: Arc<[DynamicCheck]>,
+}
+
+/// Settings a guild can override, unset ones fall back to the global config
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct GuildOverrides {
+    pub scan_channels: Option<Vec<ChannelId>>,
+    pub disabled_checks: Option<Vec<String>>,
+    pub ephemeral_scans: Option<bool>,
+    pub colors: Option<Colors>,
+}
+
+/// Embed colors per severity, unset ones use the defaults from `Severity::get_color`
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default)]
+pub struct Colors {
+    pub high: Option<u32>,
+    pub medium: Option<u32>,
+    pub none: Option<u32>,
+}
+
+impl Colors {
+    pub fn get(&self, severity: Severity) -> u32 {
+        match severity {
+            Severity::High => self.high,
+            Severity::Medium => self.medium,
+            Severity::None => self.none,
+        }
+        .unwrap_or_else(|| severity.get_color())
+    }
+
+    /// Fills the colors unset here with the ones from `other`
+    pub fn or(self, other: Colors) -> Colors {
+        Colors {
+            high: self.high.or(other.high),
+            medium: self.medium.or(other.medium),
+            none: self.none.or(other.none),
+        }
+    }
+}
+
+impl Config {
+    /// The config as seen from a guild, without copying it
+    pub fn for_guild(&self, guild: Option<GuildId>) -> GuildConfig<'_> {
+        GuildConfig {
+            config: self,
+            overrides: guild.and_then(|guild| self.guilds.get(&guild)),
+        }
+    }
+}
+
+/// A guild's overrides layered over the global settings
+#[derive(Clone, Copy)]
+pub struct GuildConfig<'a> {
+    config: &'a Config,
+    overrides: Option<&'a GuildOverrides>,
+}
+
+impl<'a> GuildConfig<'a> {
+    /// Logs are scanned everywhere unless `scan_channels` restricts it
+    pub fn should_scan(&self, channel: ChannelId) -> bool {
+        self.overrides
+            .and_then(|overrides| overrides.scan_channels.as_ref())
+            .or(self.config.scan_channels.as_ref())
+            .map_or(true, |channels| channels.contains(&channel))
+    }
+
+    pub fn disabled_checks(&self) -> &'a [String] {
+        self.overrides
+            .and_then(|overrides| overrides.disabled_checks.as_deref())
+            .unwrap_or(&self.config.disabled_checks)
+    }
+
+    pub fn ephemeral_scans(&self) -> bool {
+        self.overrides
+            .and_then(|overrides| overrides.ephemeral_scans)
+            .unwrap_or(self.config.ephemeral_scans)
+    }
+
+    pub fn colors(&self) -> Colors {
+        match self.overrides.and_then(|overrides| overrides.colors) {
+            Some(colors) => colors.or(self.config.colors),
+            None => self.config.colors,
+        }
+    }
+
+    /// The settings a guild can't override
+    pub fn global(&self) -> &'a Config {
+        self.config
+    }
+}
+
+pub fn load_config(path: impl AsRef<Path>) -> Result<Config, String> {
+    let path = path.as_ref();
+    let toml = fs::read_to_string(path)
+        .map_err(|err| format!("Couldn't read the config at `{}`: {err}", path.display()))?;
+    toml::from_str(&toml)
+        .map_err(|err| format!("Couldn't parse the config at `{}`: {err}", path.display()))
+}
+
+/// Guilds without overrides are fine, so a missing file is too
+pub fn load_guild_overrides(
+    path: impl AsRef<Path>,
+) -> io::Result<HashMap<GuildId, GuildOverrides>> {
+    match fs::read_to_string(path) {
+        Ok(json) => serde_json::from_str(&json).map_err(io::Error::from),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(HashMap::new()),
+        Err(err) => Err(err),
+    }
+}
+
+/// Serialized separately from saving, so the config doesn't stay locked while it's written
+pub fn config_toml(config: &Config) -> io::Result<String> {
+    toml::to_string(config).map_err(|err| io::Error::new(io::ErrorKind::Other, err))
+}
+
+/// Writes to a temporary file first, so a failed write can't leave a broken config behind
+pub async fn save_config(toml: String, path: impl AsRef<Path>) -> io::Result<()> {
+    let path = path.as_ref();
+    let temp = path.with_extension("toml.tmp");
+
+    tokio::fs::write(&temp, toml).await?;
+    tokio::fs::rename(temp, path).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guild_overrides_take_precedence() {
+        let mut config: Config = toml::from_str(
+            "token = \"token\"\ndisabled_checks = [\"exit_code\"]\ncolors = { high = 1, medium = 2 }\n",
+        )
+        .unwrap();
+        let guild = GuildId::new(1);
+        config.guilds.insert(
+            guild,
+            GuildOverrides {
+                scan_channels: Some(vec![ChannelId::new(10)]),
+                ephemeral_scans: Some(true),
+                colors: Some(Colors {
+                    high: Some(3),
+                    ..Colors::default()
+                }),
+                ..GuildOverrides::default()
+            },
+        );
+
+        let resolved = config.for_guild(Some(guild));
+        assert!(resolved.should_scan(ChannelId::new(10)));
+        assert!(!resolved.should_scan(ChannelId::new(11)));
+        assert!(resolved.ephemeral_scans());
+        // Unset overrides fall back to the global config
+        assert_eq!(resolved.disabled_checks(), ["exit_code"]);
+        assert_eq!(resolved.colors().high, Some(3));
+        assert_eq!(resolved.colors().medium, Some(2));
+
+        let other = config.for_guild(Some(GuildId::new(2)));
+        assert!(other.should_scan(ChannelId::new(11)));
+        assert!(!other.ephemeral_scans());
+        assert_eq!(other.colors().high, Some(1));
+    }
+
+    #[tokio::test]
+    async fn saved_config_loads_again() {
+        let mut config: Config = toml::from_str(EXAMPLE_CONFIG).unwrap();
+        config.log_extensions.push(".out".to_string());
+        config
+            .log_extensions
+            .retain(|extension| extension != ".zip");
+        config.max_log_bytes = Some(5_000_000);
+
+        let path = std::env::temp_dir().join("mental-instability-bot-saved-config.toml");
+        save_config(config_toml(&config).unwrap(), &path)
+            .await
+            .unwrap();
+        let loaded = load_config(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.token, config.token);
+        assert_eq!(loaded.log_extensions, [".log", ".log.gz", ".out"]);
+        assert_eq!(loaded.max_log_bytes, Some(5_000_000));
+    }
+
+    #[test]
+    fn guild_overrides_file_is_optional() {
+        let path = std::env::temp_dir().join("mental-instability-bot-missing-guilds.json");
+        assert!(load_guild_overrides(path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn config_without_token_is_an_error() {
+        let path = std::env::temp_dir().join("mental-instability-bot-no-token.toml");
+        fs::write(&path, "disabled_checks = [\"exit_code\"]\n").unwrap();
+        let err = load_config(&path).unwrap_err();
+        fs::remove_file(&path).unwrap();
+        assert!(err.starts_with("Couldn't parse the config"));
+        assert!(err.contains("token"));
+    }
+
+    #[test]
+    fn missing_config_is_an_error() {
+        let path = std::env::temp_dir().join("mental-instability-bot-missing-config.toml");
+        let err = load_config(path).unwrap_err();
+        assert!(err.starts_with("Couldn't read the config"));
+    }
+
+    #[test]
+    fn token_only_config_uses_defaults() {
+        let config: Config = toml::from_str(EXAMPLE_CONFIG).unwrap();
+        assert_eq!(config.token, "your bot token");
+        assert_eq!(config.log_extensions, [".log", ".log.gz", ".zip"]);
+        assert!(config.paste_services.is_empty());
+        assert!(config.disabled_checks.is_empty());
+        assert!(!config.ephemeral_scans);
+        assert!(!config.reply_in_thread);
+        assert!(config.colors.high.is_none());
+        assert!(config.max_log_bytes.is_none());
+    }
 }
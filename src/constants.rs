@@ -2,3 +2,4 @@ pub(crate) const MCLOGS_API_BASE_URL: &str = "https://api.mclo.gs";
 pub(crate) const MCLOGS_BASE_URL: &str = "https://mclo.gs";
 pub(crate) const FABRIC_META_URL: &str = "https://meta.fabricmc.net/v2";
 pub(crate) const MODRINTH_API_URL: &str = "https://api.modrinth.com/v2";
+pub(crate) const RESCAN_EMOJI: &str = "🔄";
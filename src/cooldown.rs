@@ -0,0 +1,186 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use serenity::{
+    all::{MessageId, UserId},
+    prelude::TypeMapKey,
+};
+
+pub(crate) const DEFAULT_SCAN_COOLDOWN: u64 = 10;
+pub(crate) const DEFAULT_UPLOADS_PER_MINUTE: u32 = 10;
+
+/// When each message was last scanned, so quick repeated events don't upload its logs again
+#[derive(Default)]
+pub(crate) struct Cooldowns {
+    last_scans: Mutex<HashMap<MessageId, Instant>>,
+}
+
+impl Cooldowns {
+    /// Returns whether the message may be scanned, and if so starts its cooldown
+    pub(crate) fn try_start(&self, message: MessageId, window: Duration) -> bool {
+        let mut last_scans = self.last_scans.lock().expect("Cooldowns poisoned");
+        let now = Instant::now();
+        last_scans.retain(|_, last| now.duration_since(*last) < window);
+
+        if last_scans.contains_key(&message) {
+            return false;
+        }
+        last_scans.insert(message, now);
+        true
+    }
+}
+
+pub(crate) struct CooldownData;
+
+impl TypeMapKey for CooldownData {
+    type Value = Cooldowns;
+}
+
+/// How long the logs of a scanned message are remembered for its edits
+const SCANNED_LOGS_WINDOW: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// The logs each message had when it was last scanned, so edits only rescan it for new ones
+#[derive(Default)]
+pub(crate) struct ScannedLogs {
+    messages: Mutex<HashMap<MessageId, (Instant, Vec<String>)>>,
+}
+
+impl ScannedLogs {
+    /// Remembers the message's logs and returns whether any of them weren't scanned before
+    pub(crate) fn record(&self, message: MessageId, sources: Vec<String>) -> bool {
+        let mut messages = self.messages.lock().expect("Scanned logs poisoned");
+        let now = Instant::now();
+        messages.retain(|_, (scanned, _)| now.duration_since(*scanned) < SCANNED_LOGS_WINDOW);
+
+        let is_new = match messages.get(&message) {
+            Some((_, scanned)) => sources.iter().any(|source| !scanned.contains(source)),
+            None => !sources.is_empty(),
+        };
+        messages.insert(message, (now, sources));
+        is_new
+    }
+}
+
+pub(crate) struct ScannedLogsData;
+
+impl TypeMapKey for ScannedLogsData {
+    type Value = ScannedLogs;
+}
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// A token bucket per user, so a single user can't upload lots of logs at once
+#[derive(Default)]
+pub(crate) struct UploadLimits {
+    buckets: Mutex<HashMap<UserId, Bucket>>,
+}
+
+impl UploadLimits {
+    /// Takes `count` uploads from the user's bucket, otherwise returns how long until there are enough
+    pub(crate) fn try_take(
+        &self,
+        user: UserId,
+        count: u32,
+        per_minute: u32,
+    ) -> Result<(), Duration> {
+        let capacity = f64::from(per_minute.max(1));
+        let rate = capacity / 60.0;
+        // More uploads than fit in the bucket are allowed once it's full
+        let count = f64::from(count).min(capacity);
+        let now = Instant::now();
+
+        let mut buckets = self.buckets.lock().expect("Upload limits poisoned");
+        // Full buckets are the same as no bucket
+        buckets.retain(|_, bucket| {
+            bucket.tokens + now.duration_since(bucket.updated).as_secs_f64() * rate < capacity
+        });
+        let bucket = buckets.entry(user).or_insert(Bucket {
+            tokens: capacity,
+            updated: now,
+        });
+        bucket.tokens =
+            (bucket.tokens + now.duration_since(bucket.updated).as_secs_f64() * rate).min(capacity);
+        bucket.updated = now;
+
+        if bucket.tokens >= count {
+            bucket.tokens -= count;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((count - bucket.tokens) / rate))
+        }
+    }
+}
+
+pub(crate) struct UploadLimitsData;
+
+impl TypeMapKey for UploadLimitsData {
+    type Value = UploadLimits;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_new_logs_need_a_rescan() {
+        let scanned = ScannedLogs::default();
+        let message = MessageId::new(1);
+        assert!(scanned.record(message, vec!["1".to_string()]));
+        // Fixing a typo keeps the same logs
+        assert!(!scanned.record(message, vec!["1".to_string()]));
+        assert!(scanned.record(
+            message,
+            vec!["1".to_string(), "https://mclo.gs/abc".to_string()]
+        ));
+        assert!(!scanned.record(MessageId::new(2), vec![]));
+    }
+
+    #[test]
+    fn upload_limits_take_tokens() {
+        let limits = UploadLimits::default();
+        let user = UserId::new(1);
+        assert!(limits.try_take(user, 3, 5).is_ok());
+        assert!(limits.try_take(user, 2, 5).is_ok());
+        let wait = limits.try_take(user, 1, 5).unwrap_err();
+        // A token refills every 12 seconds
+        assert!(wait > Duration::from_secs(11) && wait <= Duration::from_secs(12));
+    }
+
+    #[test]
+    fn upload_limits_are_per_user() {
+        let limits = UploadLimits::default();
+        assert!(limits.try_take(UserId::new(1), 5, 5).is_ok());
+        assert!(limits.try_take(UserId::new(2), 5, 5).is_ok());
+        assert!(limits.try_take(UserId::new(1), 1, 5).is_err());
+    }
+
+    #[test]
+    fn upload_limits_allow_a_large_batch_when_full() {
+        let limits = UploadLimits::default();
+        let user = UserId::new(1);
+        assert!(limits.try_take(user, 20, 5).is_ok());
+        assert!(limits.try_take(user, 1, 5).is_err());
+    }
+
+    #[test]
+    fn cooldown_blocks_repeated_scans() {
+        let cooldowns = Cooldowns::default();
+        let window = Duration::from_secs(60);
+        assert!(cooldowns.try_start(MessageId::new(1), window));
+        assert!(!cooldowns.try_start(MessageId::new(1), window));
+        assert!(cooldowns.try_start(MessageId::new(2), window));
+    }
+
+    #[test]
+    fn cooldown_expires() {
+        let cooldowns = Cooldowns::default();
+        assert!(cooldowns.try_start(MessageId::new(1), Duration::ZERO));
+        assert!(cooldowns.try_start(MessageId::new(1), Duration::ZERO));
+    }
+}
@@ -0,0 +1,47 @@
+use std::{
+    sync::{atomic::Ordering, Arc},
+    time::Instant,
+};
+
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+
+use crate::stats::Stats;
+
+/// Answers every request with the bot's stats, anything that connects is assumed to be a health check
+pub(crate) async fn serve(port: u16, stats: Arc<Stats>, start: Instant) -> std::io::Result<()> {
+    let listener = TcpListener::bind(("0.0.0.0", port)).await?;
+    println!("Health endpoint listening on port {port}");
+
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let stats = stats.clone();
+        tokio::spawn(async move {
+            if let Err(err) = respond(stream, &stats, start).await {
+                println!("Error answering health check: {err}");
+            }
+        });
+    }
+}
+
+async fn respond(mut stream: TcpStream, stats: &Stats, start: Instant) -> std::io::Result<()> {
+    // The request itself doesn't matter, but it has to be read before responding
+    let mut buf = [0; 1024];
+    let _ = stream.read(&mut buf).await?;
+
+    let body = serde_json::json!({
+        "status": "ok",
+        "uptime_seconds": start.elapsed().as_secs(),
+        "logs_scanned": stats.logs_scanned.load(Ordering::Relaxed),
+    })
+    .to_string();
+    let response = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
@@ -0,0 +1,14 @@
+use std::{sync::LazyLock, time::Duration};
+
+/// Shared so every request reuses the same connection pool
+pub(crate) static HTTP_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    reqwest::Client::builder()
+        .user_agent(concat!(
+            env!("CARGO_PKG_NAME"),
+            "/",
+            env!("CARGO_PKG_VERSION")
+        ))
+        .timeout(Duration::from_secs(30))
+        .build()
+        .expect("building HTTP client")
+});
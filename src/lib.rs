@@ -0,0 +1,3 @@
+#![feature(let_chains)]
+
+pub mod log_checking;
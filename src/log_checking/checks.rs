@@ -1,10 +1,14 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
 use crate::{grab, grab_all};
 
-use super::environment::{EnvironmentContext, Launcher};
-use regex::Regex;
+use super::dynamic::DynamicCheck;
+use super::environment::{parse_version, EnvironmentContext, Launcher, ModLoader, OsInfo, Side};
 
 #[allow(dead_code)]
-#[derive(PartialEq, PartialOrd, Eq, Ord, Clone, Copy)]
+#[derive(PartialEq, PartialOrd, Eq, Ord, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum Severity {
     None,
     Medium,
@@ -21,27 +25,497 @@ impl Severity {
     }
 }
 
+#[derive(Serialize)]
 pub struct CheckReport {
     pub title: String,
     pub description: String,
     pub severity: Severity,
 }
 
-pub fn check_checks(log: &str, ctx: &EnvironmentContext) -> Vec<CheckReport> {
-    [
-        crash_report_analysis,
-        dependency_generic,
-        crash_generic,
-        java,
-        missing_field,
-        polymc,
-        optifabric,
-        bclib,
-        indium,
-    ]
-    .iter()
-    .filter_map(|check| check(log, ctx))
-    .collect()
+pub struct Check {
+    pub id: &'static str,
+    pub title: &'static str,
+    pub summary: &'static str,
+    pub severity: Severity,
+    pub run: CheckFn,
+}
+
+pub enum CheckFn {
+    Log(fn(&str, &EnvironmentContext) -> Option<CheckReport>),
+    /// For checks with thresholds that can be configured
+    Configured(fn(&str, &EnvironmentContext, &CheckSettings) -> Option<CheckReport>),
+}
+
+pub const CHECKS: &[Check] = &[
+    Check {
+        id: "crash_report_analysis",
+        title: "Crash report analysis",
+        summary: "Shows the description and error of a Minecraft crash report.",
+        severity: Severity::None,
+        run: CheckFn::Log(crash_report_analysis),
+    },
+    Check {
+        id: "suspected_mods",
+        title: "Suspected mods",
+        summary: "Lists the mods a crash report names as likely causes.",
+        severity: Severity::None,
+        run: CheckFn::Log(suspected_mods),
+    },
+    Check {
+        id: "dependency_generic",
+        title: "Missing dependency",
+        summary: "A mod needs another mod that isn't installed.",
+        severity: Severity::High,
+        run: CheckFn::Log(dependency_generic),
+    },
+    Check {
+        id: "forge_dependency",
+        title: "Forge dependency missing",
+        summary: "A Forge or NeoForge mod's dependency is missing or has the wrong version.",
+        severity: Severity::High,
+        run: CheckFn::Log(forge_dependency),
+    },
+    Check {
+        id: "neoforge_incompatible",
+        title: "Mod incompatible with NeoForge",
+        summary: "A mod needs a different NeoForge version.",
+        severity: Severity::High,
+        run: CheckFn::Log(neoforge_incompatible),
+    },
+    Check {
+        id: "incompatible_mod_set",
+        title: "Incompatible mod set",
+        summary: "Several mods are missing or conflict with each other.",
+        severity: Severity::High,
+        run: CheckFn::Log(incompatible_mod_set),
+    },
+    Check {
+        id: "crash_generic",
+        title: "Mixin and entrypoint errors",
+        summary: "A mod failed to apply its mixins or run its entrypoint.",
+        severity: Severity::High,
+        run: CheckFn::Log(crash_generic),
+    },
+    Check {
+        id: "mixin_target_missing",
+        title: "Mixin target not found",
+        summary: "A mixin targets a class that doesn't exist, usually from a missing mod.",
+        severity: Severity::High,
+        run: CheckFn::Log(mixin_target_missing),
+    },
+    Check {
+        id: "refmap_missing",
+        title: "Mixin refmap missing",
+        summary: "A mod was built without its mixin reference map.",
+        severity: Severity::Medium,
+        run: CheckFn::Log(refmap_missing),
+    },
+    Check {
+        id: "mixin_conflict",
+        title: "Mixin conflict",
+        summary: "Two mods change the same part of the game and conflict.",
+        severity: Severity::Medium,
+        run: CheckFn::Log(mixin_conflict),
+    },
+    Check {
+        id: "java",
+        title: "Incorrect Java version",
+        summary: "The game or a mod needs a different Java version.",
+        severity: Severity::High,
+        run: CheckFn::Log(java),
+    },
+    Check {
+        id: "java_path",
+        title: "Invalid Java path",
+        summary: "The launcher points to a Java installation that doesn't exist.",
+        severity: Severity::High,
+        run: CheckFn::Log(java_path),
+    },
+    Check {
+        id: "missing_field",
+        title: "Field missing error",
+        summary: "A mod accesses a field stripped on the logical server.",
+        severity: Severity::High,
+        run: CheckFn::Log(missing_field),
+    },
+    Check {
+        id: "polymc",
+        title: "PolyMC detected",
+        summary: "The outdated PolyMC launcher is being used.",
+        severity: Severity::Medium,
+        run: CheckFn::Log(polymc),
+    },
+    Check {
+        id: "optifabric",
+        title: "OptiFabric detected",
+        summary: "OptiFine is loaded on Fabric through OptiFabric.",
+        severity: Severity::High,
+        run: CheckFn::Log(optifabric),
+    },
+    Check {
+        id: "optifine",
+        title: "OptiFine crash",
+        summary: "The game crashed inside of OptiFine.",
+        severity: Severity::High,
+        run: CheckFn::Log(optifine),
+    },
+    Check {
+        id: "bclib",
+        title: "BCLib detected",
+        summary: "BCLib, which causes issues with some mods, is installed.",
+        severity: Severity::Medium,
+        run: CheckFn::Log(bclib),
+    },
+    Check {
+        id: "indium",
+        title: "Missing Indium",
+        summary: "A mod needs the Fabric Rendering API while Sodium is loaded without Indium.",
+        severity: Severity::High,
+        run: CheckFn::Log(indium),
+    },
+    Check {
+        id: "out_of_memory",
+        title: "Out of memory",
+        summary: "The game ran out of heap space or metaspace.",
+        severity: Severity::High,
+        run: CheckFn::Log(out_of_memory),
+    },
+    Check {
+        id: "large_modpack",
+        title: "Large modpack",
+        summary: "Many mods are installed, which needs a lot of memory.",
+        severity: Severity::Medium,
+        run: CheckFn::Configured(large_modpack),
+    },
+    Check {
+        id: "memory_exceeds_system",
+        title: "Too much memory allocated",
+        summary: "More memory is allocated than the system has.",
+        severity: Severity::Medium,
+        run: CheckFn::Log(memory_exceeds_system),
+    },
+    Check {
+        id: "gc_flags",
+        title: "No garbage collector flags",
+        summary: "A server with a lot of memory doesn't tune its garbage collector.",
+        severity: Severity::None,
+        run: CheckFn::Log(gc_flags),
+    },
+    Check {
+        id: "forge_on_fabric",
+        title: "Forge mod on Fabric",
+        summary: "A Forge mod was put into a Fabric instance.",
+        severity: Severity::High,
+        run: CheckFn::Log(forge_on_fabric),
+    },
+    Check {
+        id: "fabric_api_outdated",
+        title: "Fabric API version mismatch",
+        summary: "Fabric API is made for a different Minecraft version.",
+        severity: Severity::Medium,
+        run: CheckFn::Log(fabric_api_outdated),
+    },
+    Check {
+        id: "fabric_loader_outdated",
+        title: "Fabric Loader outdated",
+        summary: "A mod needs a newer version of Fabric Loader.",
+        severity: Severity::High,
+        run: CheckFn::Log(fabric_loader_outdated),
+    },
+    Check {
+        id: "java_32bit",
+        title: "32-bit Java",
+        summary: "The game runs on a 32-bit Java installation.",
+        severity: Severity::High,
+        run: CheckFn::Log(java_32bit),
+    },
+    Check {
+        id: "gpu_driver",
+        title: "Graphics driver issue",
+        summary: "The GPU driver couldn't create an OpenGL context.",
+        severity: Severity::Medium,
+        run: CheckFn::Log(gpu_driver),
+    },
+    Check {
+        id: "shader_compilation",
+        title: "Shader compilation failed",
+        summary: "A shader pack's shader failed to compile.",
+        severity: Severity::Medium,
+        run: CheckFn::Log(shader_compilation),
+    },
+    Check {
+        id: "shaders_without_loader",
+        title: "Shaders without a shader loader",
+        summary: "A shader pack was added without Iris or OptiFine to load it.",
+        severity: Severity::Medium,
+        run: CheckFn::Log(shaders_without_loader),
+    },
+    Check {
+        id: "resource_pack_error",
+        title: "Broken resource pack",
+        summary: "A resource pack couldn't be loaded.",
+        severity: Severity::Medium,
+        run: CheckFn::Log(resource_pack_error),
+    },
+    Check {
+        id: "datapack_error",
+        title: "Broken data pack",
+        summary: "A data pack of the world couldn't be loaded.",
+        severity: Severity::Medium,
+        run: CheckFn::Log(datapack_error),
+    },
+    Check {
+        id: "sodium_iris",
+        title: "Sodium and Iris mismatch",
+        summary: "Sodium and Iris versions that don't work together are installed.",
+        severity: Severity::High,
+        run: CheckFn::Log(sodium_iris),
+    },
+    Check {
+        id: "sodium_version_mismatch",
+        title: "Sodium version mismatch",
+        summary: "Sodium is made for a different Minecraft version.",
+        severity: Severity::High,
+        run: CheckFn::Log(sodium_version_mismatch),
+    },
+    Check {
+        id: "corrupted_jar",
+        title: "Corrupted jar",
+        summary: "A mod or library jar is corrupted or incompletely downloaded.",
+        severity: Severity::High,
+        run: CheckFn::Log(corrupted_jar),
+    },
+    Check {
+        id: "minecraft_version_mismatch",
+        title: "Wrong Minecraft version",
+        summary: "A mod is made for a different Minecraft version.",
+        severity: Severity::High,
+        run: CheckFn::Log(minecraft_version_mismatch),
+    },
+    Check {
+        id: "native_library",
+        title: "Native library failed to load",
+        summary: "An LWJGL native library is missing or blocked.",
+        severity: Severity::High,
+        run: CheckFn::Log(native_library),
+    },
+    Check {
+        id: "rosetta",
+        title: "Wrong Java architecture on Apple Silicon",
+        summary: "An Intel Java build or natives are used on an Apple Silicon Mac.",
+        severity: Severity::High,
+        run: CheckFn::Log(rosetta),
+    },
+    Check {
+        id: "environment_mismatch",
+        title: "Mod on the wrong side",
+        summary: "A client-only mod is loaded on a server, or the other way around.",
+        severity: Severity::High,
+        run: CheckFn::Log(environment_mismatch),
+    },
+    Check {
+        id: "disk_full",
+        title: "Disk full",
+        summary: "There is no space left on the disk.",
+        severity: Severity::High,
+        run: CheckFn::Log(disk_full),
+    },
+    Check {
+        id: "connection_refused",
+        title: "Server unreachable",
+        summary: "The client couldn't connect to a server.",
+        severity: Severity::Medium,
+        run: CheckFn::Log(connection_refused),
+    },
+    Check {
+        id: "auth_servers_down",
+        title: "Authentication servers unreachable",
+        summary: "Mojang's authentication servers couldn't be reached.",
+        severity: Severity::Medium,
+        run: CheckFn::Log(auth_servers_down),
+    },
+    Check {
+        id: "invalid_session",
+        title: "Invalid session",
+        summary: "The account's login session is invalid or expired.",
+        severity: Severity::Medium,
+        run: CheckFn::Log(invalid_session),
+    },
+    Check {
+        id: "world_corruption",
+        title: "World corruption",
+        summary: "Part of the world save couldn't be loaded.",
+        severity: Severity::High,
+        run: CheckFn::Log(world_corruption),
+    },
+    Check {
+        id: "library_mismatch",
+        title: "Library version mismatch",
+        summary: "A mod needs a different version of a library like Architectury or Cloth Config.",
+        severity: Severity::High,
+        run: CheckFn::Log(library_mismatch),
+    },
+    Check {
+        id: "fabric_api_mismatch",
+        title: "Fabric API method missing",
+        summary: "A mod uses a Fabric API method that doesn't exist in the installed version.",
+        severity: Severity::High,
+        run: CheckFn::Log(fabric_api_mismatch),
+    },
+    Check {
+        id: "api_mismatch",
+        title: "Missing method or class",
+        summary: "A mod calls code that doesn't exist, usually from mismatched mod versions.",
+        severity: Severity::Medium,
+        run: CheckFn::Log(api_mismatch),
+    },
+    Check {
+        id: "download_failed",
+        title: "Download failed",
+        summary: "The launcher couldn't download a file the game needs.",
+        severity: Severity::Medium,
+        run: CheckFn::Log(download_failed),
+    },
+    Check {
+        id: "ssl_handshake",
+        title: "Secure connection failed",
+        summary: "An SSL/TLS handshake failed, often from a wrong clock or a proxy.",
+        severity: Severity::Medium,
+        run: CheckFn::Log(ssl_handshake),
+    },
+    Check {
+        id: "server_overloaded",
+        title: "Server overloaded",
+        summary: "The server repeatedly couldn't keep up with its tick rate.",
+        severity: Severity::Medium,
+        run: CheckFn::Log(server_overloaded),
+    },
+    Check {
+        id: "server_watchdog",
+        title: "Server watchdog crash",
+        summary: "The server froze and was shut down by its watchdog.",
+        severity: Severity::High,
+        run: CheckFn::Log(server_watchdog),
+    },
+    Check {
+        id: "known_mod_crash",
+        title: "Known mod crash",
+        summary: "A mod with recurring crashes shows up in the stack trace.",
+        severity: Severity::Medium,
+        run: CheckFn::Log(known_mod_crash),
+    },
+];
+
+/// Only run when no other check found anything
+pub const FALLBACK_CHECKS: &[Check] = &[
+    Check {
+        id: "exit_code",
+        title: "Abnormal exit code",
+        summary: "The game exited with an error code without any other known issue.",
+        severity: Severity::None,
+        run: CheckFn::Log(exit_code),
+    },
+    Check {
+        id: "launcher_log",
+        title: "Launcher log",
+        summary: "The log is from the launcher instead of the game.",
+        severity: Severity::None,
+        run: CheckFn::Log(launcher_log),
+    },
+];
+
+pub fn all_checks() -> impl Iterator<Item = &'static Check> {
+    CHECKS.iter().chain(FALLBACK_CHECKS)
+}
+
+/// How a built-in or dynamic check is listed to users
+pub struct CheckListing<'a> {
+    pub id: &'static str,
+    pub title: &'a str,
+    pub summary: &'a str,
+    pub severity: Severity,
+}
+
+/// Every check that can report something, including the ones loaded from `checks.toml`
+pub fn registered_checks(
+    dynamic_checks: &[DynamicCheck],
+) -> impl Iterator<Item = CheckListing<'_>> {
+    let built_in = all_checks().map(|check| CheckListing {
+        id: check.id,
+        title: check.title,
+        summary: check.summary,
+        severity: check.severity,
+    });
+    let dynamic = dynamic_checks.iter().map(|check| CheckListing {
+        id: check.id,
+        title: &check.title,
+        summary: "A pattern from `checks.toml`.",
+        severity: check.severity,
+    });
+    built_in.chain(dynamic)
+}
+
+pub const DEFAULT_LARGE_MODPACK_THRESHOLD: usize = 400;
+/// A rough estimate of the heap each mod needs, large packs of around 400 mods usually
+/// recommend 6 to 8 GB
+pub const DEFAULT_MEMORY_PER_MOD_MB: u64 = 16;
+
+pub struct CheckSettings {
+    pub disabled_checks: Vec<String>,
+    pub large_modpack_threshold: usize,
+    pub memory_per_mod_mb: u64,
+    pub dynamic_checks: Arc<[DynamicCheck]>,
+}
+
+impl Default for CheckSettings {
+    fn default() -> Self {
+        Self {
+            disabled_checks: vec![],
+            large_modpack_threshold: DEFAULT_LARGE_MODPACK_THRESHOLD,
+            memory_per_mod_mb: DEFAULT_MEMORY_PER_MOD_MB,
+            dynamic_checks: Arc::default(),
+        }
+    }
+}
+
+impl CheckSettings {
+    fn is_enabled(&self, id: &str) -> bool {
+        !self.disabled_checks.iter().any(|disabled| disabled == id)
+    }
+}
+
+pub fn check_checks(
+    log: &str,
+    ctx: &EnvironmentContext,
+    settings: &CheckSettings,
+) -> Vec<(&'static str, CheckReport)> {
+    let run = |checks: &'static [Check]| {
+        checks
+            .iter()
+            .filter(|check| settings.is_enabled(check.id))
+            .filter_map(|check| {
+                let report = match check.run {
+                    CheckFn::Log(run) => run(log, ctx),
+                    CheckFn::Configured(run) => run(log, ctx, settings),
+                };
+                Some((check.id, report?))
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut reports = run(CHECKS);
+    reports.extend(
+        settings
+            .dynamic_checks
+            .iter()
+            .filter(|check| settings.is_enabled(check.id))
+            .filter_map(|check| Some((check.id, check.run(log)?))),
+    );
+    if reports.is_empty() {
+        run(FALLBACK_CHECKS)
+    } else {
+        reports
+    }
 }
 
 pub fn crash_report_analysis(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
@@ -50,7 +524,29 @@ pub fn crash_report_analysis(log: &str, _ctx: &EnvironmentContext) -> Option<Che
         r"---- Minecraft Crash Report ----\n// .+\n\nTime: .+\nDescription: (.+)\n\n(.+)\n"
     ) {
         let description = captures.get(1).expect("Regex err").as_str();
-        let error = captures.get(2).expect("Regex err 2").as_str();
+        let mut error = captures.get(2).expect("Regex err 2").as_str().to_string();
+
+        // The first frames usually show which mod crashed
+        let frames = log[captures.get(0).expect("Regex err 3").end()..]
+            .lines()
+            .take_while(|line| line.trim_start().starts_with("at "))
+            .take(8);
+        for frame in frames {
+            error.push('\n');
+            error.push_str(frame.trim_end());
+        }
+
+        // Embed fields are limited to 1024 characters
+        let limit = 1000 - description.len().min(500);
+        if error.len() > limit {
+            let mut end = limit;
+            while !error.is_char_boundary(end) {
+                end -= 1;
+            }
+            error.truncate(end);
+            error.push_str("...");
+        }
+
         return Some(CheckReport {
             title: "Crash report analysis".to_string(),
             description: format!("Context: `{description}`\n```\n{error}\n```"),
@@ -61,11 +557,16 @@ pub fn crash_report_analysis(log: &str, _ctx: &EnvironmentContext) -> Option<Che
 }
 
 pub fn dependency_generic(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
+    // Several problems at once are reported by incompatible_mod_set instead
+    if incompatible_mod_set_problems(log).len() > 1 {
+        return None;
+    }
     if let Some(captures) = grab_all!(
         log,
         r"Mod '(.+)' \(\S+\) \S+ requires any version between \S+ and \S+ of (.+), which is missing!",
         r"Mod '(.+)' \(\S+\) \S+ requires version \S+ or later of (.+), which is missing!",
-        r"Mod '(.+)' \(\S+\) \S+ requires any version of (.+), which is missing!"
+        r"Mod '(.+)' \(\S+\) \S+ requires any version of (.+), which is missing!",
+        r"Mod '(.+)' \(\S+\) \S+ requires [^\n]+? of mod '?([^'(),\n]+?)'?(?: \(\S+\))?, which is missing!"
     ) {
         let dependent = captures.get(1).expect("Regex err").as_str();
         let dependency = captures.get(2).expect("Regex err 2").as_str();
@@ -108,7 +609,7 @@ pub fn crash_generic(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport
 
     if let Some(Some(mod_id)) = grab!(
         log,
-        r"RuntimeException: Could not execute entrypoint stage '\S+' due to errors, provided by '(\S+)'!"
+        r"RuntimeException: Could not execute entrypoint stage '\S+' due to errors, provided by '(\S+?)'(?: at '\S+')?!"
     ) {
         return Some(CheckReport {
             title: "Entrypoint error".to_string(),
@@ -142,7 +643,7 @@ pub fn match_java_classfile_version(classfile_version: &str) -> Option<&'static
     }
 }
 
-pub fn java(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
+pub fn java(log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
     if let Some(captures) = grab_all!(
         log,
         r"- Replace '.+' \(java\) ([0-9]+) with version ([0-9]+) or later\."
@@ -171,6 +672,12 @@ pub fn java(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
                 format!(
                     "A mod or Minecraft itself requires Java {need} to be used, but an older version, Java {has} is being used instead. You may have to [download](https://adoptium.net/temurin/releases/?version={need}) a newer Java version and/or select it in your launcher."
                 )
+            } else if let Some(version) = &ctx.mc_version
+                && let Some(need) = ctx.required_java_version()
+            {
+                format!(
+                    "A mod or Minecraft itself requires a different version of Java from the one that is available. Minecraft `{version}` runs on Java {need}, you may have to [download](https://adoptium.net/temurin/releases/?version={need}) it and/or select it in your launcher."
+                )
             } else {
                 "A mod or Minecraft itself requires a different version of Java from the one that is available. You may have to [download](https://adoptium.net/temurin/releases/) a newer Java version and/or select it in your launcher.".to_string()
             },
@@ -182,11 +689,22 @@ pub fn java(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
 
 // java.lang.NoSuchFieldError
 
-pub fn missing_field(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
+pub fn missing_field(log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
+    if matches!(ctx.loader, Some(ModLoader::Forge | ModLoader::NeoForge)) {
+        return None;
+    }
     if grab!(log, r"java\.lang\.NoSuchFieldError").is_some() {
+        let mut description = if matches!(ctx.loader, Some(ModLoader::Quilt(_))) {
+            "On the logical server some fields may be deleted by Quilt Loader when a mod defines them as client-only, and some mods may have implemented this incorrectly. See if there's an update for the mod in question.".to_string()
+        } else {
+            "On the logical server some fields may be deleted by Fabric Loader when a mod defines them as client-only. Since this feature was broken before loader `0.15`, some mods may have implemented it incorrectly. See if there's an update for the mod in question, or try downgrading Fabric Loader.".to_string()
+        };
+        if ctx.side == Some(Side::Server) {
+            description.push_str(" Since this is a dedicated server, also make sure the mod isn't meant for the client only.");
+        }
         return Some(CheckReport {
             title: "Field missing error".to_string(),
-            description: "On the logical server some fields may be deleted by Fabric Loader when a mod defines them as client-only. Since this feature was broken before loader `0.15`, some mods may have implemented it incorrectly. See if there's an update for the mod in question, or try downgrading Fabric Loader.".to_string(),
+            description,
             severity: Severity::High,
         });
     }
@@ -237,7 +755,10 @@ pub fn bclib(_log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
     None
 }
 
-pub fn indium(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
+pub fn indium(log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
+    if matches!(ctx.loader, Some(ModLoader::Forge | ModLoader::NeoForge)) {
+        return None;
+    }
     if grab!(
             log,
             r#"because the return value of "net\.fabricmc\.fabric\.api\.renderer\.v1\.RendererAccess\.getRenderer\(\)" is null"#
@@ -252,3 +773,1719 @@ pub fn indium(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
     }
     None
 }
+
+/// Below this, running out of heap space most likely just needs a bigger allocation
+const MIN_MODDED_MEMORY_MB: u64 = 4096;
+
+pub fn out_of_memory(log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
+    if grab!(log, r"java\.lang\.OutOfMemoryError: Metaspace").is_some() {
+        return Some(CheckReport {
+            title: "Out of metaspace".to_string(),
+            description: "The game ran out of metaspace, the memory Java uses to store loaded classes. This is not fixed by allocating more RAM; a `-XX:MaxMetaspaceSize` argument in your launcher's JVM arguments may be limiting it, so remove it or raise it. Otherwise, a mod may be leaking classes and should be updated or removed.".to_string(),
+            severity: Severity::High,
+        });
+    }
+    if grab!(
+        log,
+        r"java\.lang\.OutOfMemoryError: Java heap space",
+        r"java\.lang\.OutOfMemoryError: GC overhead limit exceeded"
+    )
+    .is_some()
+    {
+        // An unknown allocation also compares as too low, which is the more likely cause
+        let description = if ctx.max_memory_mb < Some(MIN_MODDED_MEMORY_MB) {
+            format!(
+                "The game ran out of allocated memory{}. Consider allocating more RAM, at least 4 GB for modded instances:\n- **Prism/MultiMC:** Edit Instance → Settings → Java → Maximum memory allocation\n- **Vanilla launcher:** Installations → Edit → More Options → change `-Xmx2G` in JVM arguments to e.g. `-Xmx4G`\n- **CurseForge:** Settings → Minecraft → Java Settings → Allocated Memory",
+                ctx.max_memory_mb
+                    .map(|memory| format!(" (`{memory}` MiB)"))
+                    .unwrap_or_default()
+            )
+        } else {
+            format!(
+                "The game ran out of memory even with `{}` MiB allocated, which is enough for most modpacks. This usually means a mod is leaking memory, so check which mods were added or updated recently and try removing them.",
+                ctx.max_memory_mb.unwrap_or_default()
+            )
+        };
+        return Some(CheckReport {
+            title: "Out of memory".to_string(),
+            description,
+            severity: Severity::High,
+        });
+    }
+    None
+}
+
+pub fn forge_on_fabric(log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
+    if matches!(ctx.loader, Some(ModLoader::Forge | ModLoader::NeoForge)) {
+        return None;
+    }
+    if let Some(captures) = grab_all!(
+        log,
+        r"FormattedException: .*?(\S+\.jar)\S* .*this mod is for Forge",
+        r"(\S+\.jar)\S* (?:is|appears to be) a (?:Neo)?Forge mod",
+        r"Found a (?:Neo)?Forge mod(?: file)? (\S+\.jar)",
+        r"(\S+\.jar)\S* is a (?:Neo)?Forge mod; Quilt Loader cannot load",
+        r"FormattedException: .*this mod is for Forge"
+    ) {
+        let loader = if matches!(ctx.loader, Some(ModLoader::Quilt(_))) {
+            "Quilt"
+        } else {
+            "Fabric"
+        };
+        let description = if let Some(file) = captures.get(1) {
+            format!(
+                "The file `{}` is a Forge mod, which cannot be loaded by {loader}. Remove it from your mods folder, or replace it with the Fabric version of the mod if there is one.",
+                file.as_str().trim_matches(|c: char| c == '\'' || c == '"')
+            )
+        } else {
+            format!("A Forge mod was found in the mods folder, which cannot be loaded by {loader}. Remove it, or replace it with the Fabric version of the mod if there is one.")
+        };
+        return Some(CheckReport {
+            title: "Forge mod on Fabric".to_string(),
+            description,
+            severity: Severity::High,
+        });
+    }
+    None
+}
+
+fn same_version(a: &[u32], b: &[u32]) -> bool {
+    (0..a.len().max(b.len())).all(|i| a.get(i).unwrap_or(&0) == b.get(i).unwrap_or(&0))
+}
+
+pub fn fabric_api_outdated(_log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
+    let fabric_api = ctx
+        .known_mods
+        .iter()
+        .find(|m| m.0 .0 == "fabric-api" || m.0 .0 == "fabric")?;
+    let (_, api_minecraft) = fabric_api.1.split_once('+')?;
+    let game = parse_version(ctx.mc_version.as_ref()?)?;
+    let target = parse_version(api_minecraft)?;
+
+    if !same_version(&game, &target) {
+        return Some(CheckReport {
+            title: "Fabric API version mismatch".to_string(),
+            description: format!(
+                "The installed Fabric API `{}` is made for Minecraft `{api_minecraft}`, but the game is running `{}`. Download the matching version of [Fabric API](https://modrinth.com/mod/fabric-api/versions) from Modrinth.",
+                fabric_api.1,
+                ctx.mc_version.as_ref()?
+            ),
+            severity: Severity::Medium,
+        });
+    }
+    None
+}
+
+pub fn java_32bit(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
+    if grab!(
+        log,
+        r"Java is version \S+, using 32 \(\S+\) architecture",
+        r"\n\s*Java VM Version: [^\n]*32-Bit",
+        r"\n\s*Java VM Version: Java HotSpot\(TM\) Client VM"
+    )
+    .is_some()
+    {
+        return Some(CheckReport {
+            title: "32-bit Java".to_string(),
+            description: "The game is running on a 32-bit version of Java, which cannot use more than ~4 GB of memory and is not supported by modern Minecraft versions. [Download](https://adoptium.net/temurin/releases/?arch=x64) a 64-bit Java version and select it in your launcher.".to_string(),
+            severity: Severity::High,
+        });
+    }
+    None
+}
+
+pub fn gpu_driver(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
+    if grab!(
+        log,
+        r"Pixel format not accelerated",
+        r"WGL: The driver does not appear to support OpenGL",
+        r"GLFW error 65543",
+        r"Couldn't set pixel format",
+        r"OpenGL \d\.\d+ or higher is required",
+        r"RenderSystem: .*GL_ARB_\w+ (?:is )?(?:not supported|missing)"
+    )
+    .is_some()
+    {
+        let renderer = grab_all!(
+            log,
+            r"Renderer: '([^'\r\n]+)'",
+            r"Backend API: ([^\r\n]+?) GL version",
+            r"Graphics card #0 name: ([^\r\n]+)"
+        )
+        .and_then(|captures| captures.get(1))
+        .map(|renderer| renderer.as_str().trim());
+
+        let description = if let Some(renderer) = renderer {
+            format!("The graphics driver for `{renderer}` failed to create a usable OpenGL context. Update your GPU drivers from the manufacturer's website. If this is an old integrated GPU, such as Intel HD Graphics, it may not support the OpenGL version this Minecraft version needs.")
+        } else {
+            "The graphics driver failed to create a usable OpenGL context. Update your GPU drivers from the manufacturer's website. If you're using an old integrated GPU, such as Intel HD Graphics, it may not support the OpenGL version this Minecraft version needs.".to_string()
+        };
+
+        return Some(CheckReport {
+            title: "Graphics driver issue".to_string(),
+            description,
+            severity: Severity::Medium,
+        });
+    }
+    None
+}
+
+pub fn sodium_iris(log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
+    if grab!(
+        log,
+        r"Mod 'Iris' \(iris\) \S+ requires [^\n]+ of mod 'Sodium' \(sodium\), but only the wrong version is present",
+        r"Mod 'Sodium' \(sodium\) \S+ is incompatible with [^\n]+ of mod 'Iris' \(iris\)",
+        r"Mod 'Iris' \(iris\) \S+ is incompatible with [^\n]+ of mod 'Sodium' \(sodium\)",
+        r"IncompatibleIris",
+        r"Sodium has detected an incompatible version of Iris",
+        r"Iris [^\n]*incompatible (?:version of )?Sodium"
+    )
+    .is_some()
+    {
+        let sodium = ctx.known_mods.iter().find(|m| m.0 .0 == "sodium");
+        let iris = ctx.known_mods.iter().find(|m| m.0 .0 == "iris");
+
+        let description = if let Some(sodium) = sodium
+            && let Some(iris) = iris
+        {
+            format!(
+                "Sodium `{}` and Iris `{}` are not compatible with each other. Install versions of [Sodium](https://modrinth.com/mod/sodium/versions) and [Iris](https://modrinth.com/mod/iris/versions) that are made to work together for your Minecraft version.",
+                sodium.1, iris.1
+            )
+        } else {
+            "The installed versions of Sodium and Iris are not compatible with each other. Install versions of [Sodium](https://modrinth.com/mod/sodium/versions) and [Iris](https://modrinth.com/mod/iris/versions) that are made to work together for your Minecraft version.".to_string()
+        };
+
+        return Some(CheckReport {
+            title: "Sodium and Iris mismatch".to_string(),
+            description,
+            severity: Severity::High,
+        });
+    }
+    None
+}
+
+pub fn corrupted_jar(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
+    if let Some(captures) = grab_all!(
+        log,
+        r"Error analyzing \[?([^\]\r\n]+?\.jar)\]?: java\.util\.zip\.Zip(?:Exception|Error)",
+        r"([^\s\[\]'\x22]+\.jar)[^\r\n]*(?:ZipException|ZipError|[Ii]nvalid CEN header|Unexpected end of ZLIB input stream|zip END header not found)",
+        r"java\.util\.zip\.Zip(?:Exception|Error)",
+        r"[Ii]nvalid CEN header",
+        r"Unexpected end of ZLIB input stream"
+    ) {
+        let description = if let Some(path) = captures.get(1) {
+            let file = path
+                .as_str()
+                .rsplit(['/', '\\'])
+                .next()
+                .unwrap_or(path.as_str());
+            format!("The file `{file}` is corrupted or was only partially downloaded. Delete it and download it again.")
+        } else {
+            "A mod or library file is corrupted or was only partially downloaded. Delete any recently added mods and download them again, or reinstall the instance.".to_string()
+        };
+
+        return Some(CheckReport {
+            title: "Corrupted jar".to_string(),
+            description,
+            severity: Severity::High,
+        });
+    }
+    None
+}
+
+pub fn minecraft_version_mismatch(log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
+    if let Some(captures) = grab_all!(
+        log,
+        r"Mod '(.+?)' \(\S+\) \S+ requires (.+?) of (?:mod )?'?Minecraft'? \(minecraft\), but only the wrong version is present: ([^\s!]+)!",
+        r"Mod '(.+?)' \(\S+\) \S+ requires (.+?) of minecraft, but only the wrong version is present: ([^\s!]+)!"
+    ) {
+        let mod_name = captures.get(1).expect("Regex err").as_str();
+        let required = captures.get(2).expect("Regex err 2").as_str();
+        let actual = captures.get(3).expect("Regex err 3").as_str();
+        // The loader's view of the version is what the mod was checked against
+        let detected = match &ctx.mc_version {
+            Some(version) if version != actual => {
+                format!(" The log also mentions Minecraft `{version}`.")
+            }
+            _ => String::new(),
+        };
+        return Some(CheckReport {
+            title: "Wrong Minecraft version".to_string(),
+            description: format!("The `{mod_name}` mod requires {required} of Minecraft, but the game is running `{actual}`. Download the version of `{mod_name}` made for `{actual}`, or play on a Minecraft version the mod supports.{detected}"),
+            severity: Severity::High,
+        });
+    }
+    None
+}
+
+pub fn mixin_target_missing(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
+    if let Some(captures) = grab_all!(
+        log,
+        r"InvalidMixinException[^\n]*@Mixin target (\S+) was not found (\S+?\.json)",
+        r"/ERROR\][^\n]*@Mixin target (\S+) was not found (\S+?\.json)",
+        r"ClassMetadataNotFoundException: (\S+)"
+    ) {
+        let class = captures
+            .get(1)
+            .expect("Regex err")
+            .as_str()
+            .replace('/', ".");
+        let description = if let Some(config) = captures.get(2) {
+            format!("The mixin config `{}` targets the class `{class}`, which could not be found. This usually means a mod it depends on is missing or has the wrong version, check the requirements of the mod providing this mixin.", config.as_str())
+        } else {
+            format!("A mixin targets the class `{class}`, which could not be found. This usually means a mod it depends on is missing or has the wrong version, check the requirements of the mod providing this mixin.")
+        };
+        return Some(CheckReport {
+            title: "Mixin target not found".to_string(),
+            description,
+            severity: Severity::High,
+        });
+    }
+    None
+}
+
+pub fn native_library(log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
+    if let Some(captures) = grab_all!(
+        log,
+        r"Failed to locate library: (\S+)",
+        r"UnsatisfiedLinkError: [^\n]*?([\w\-.]+\.(?:dll|so|dylib))",
+        r"Can't load library: (\S+)",
+        r"no (\S+) in java\.library\.path"
+    ) {
+        let library = captures.get(1).expect("Regex err").as_str();
+        let library = library.rsplit(['/', '\\']).next().unwrap_or(library);
+        let os = ctx.os.as_ref();
+        let advice = if library.ends_with(".dll") || os.is_some_and(OsInfo::is_windows) {
+            "On Windows, this is often caused by an antivirus deleting or blocking the file. Add an exception for your launcher's folder and reinstall the instance."
+        } else if library.ends_with(".dylib") || os.is_some_and(OsInfo::is_mac) {
+            "On macOS, make sure the Java version matches your Mac's architecture, and reinstall the instance."
+        } else if library.ends_with(".so") || os.is_some_and(OsInfo::is_linux) {
+            "On Linux, make sure the required system libraries are installed and the Java version matches your system's architecture, then reinstall the instance."
+        } else {
+            "Check that no antivirus is blocking your launcher's files, then reinstall the instance."
+        };
+        return Some(CheckReport {
+            title: "Native library failed to load".to_string(),
+            description: format!(
+                "The native library `{library}` needed by LWJGL could not be loaded. {advice}"
+            ),
+            severity: Severity::High,
+        });
+    }
+    None
+}
+
+pub fn rosetta(log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
+    let on_mac = match &ctx.os {
+        Some(os) => os.is_mac(),
+        None => grab!(log, r"Mac OS X", r"macOS").is_some(),
+    };
+    let apple_silicon = grab!(log, r"Apple M\d", r"\(aarch64\)", r"\barm64\b").is_some();
+    let x86_java = grab!(
+        log,
+        r"Java is version \S+, using 64 \((?:x86_64|amd64)\) architecture",
+        r"Operating System: Mac OS X \((?:x86_64|amd64)\)"
+    )
+    .is_some();
+
+    if grab!(
+        log,
+        r"incompatible architecture \(have '?(?:x86_64|arm64)'?, need '?(?:x86_64|arm64)'?\)"
+    )
+    .is_some()
+        || (on_mac && apple_silicon && x86_java)
+        || (on_mac && apple_silicon && grab!(log, r"no lwjgl\S* in java\.library\.path").is_some())
+    {
+        return Some(CheckReport {
+            title: "Wrong Java architecture on Apple Silicon".to_string(),
+            description: "This Mac has an Apple Silicon (ARM) processor, but the game's Java or native libraries are made for Intel processors, which doesn't work through Rosetta. [Download](https://adoptium.net/temurin/releases/?os=mac&arch=aarch64) an `aarch64` Java version and select it in your launcher.".to_string(),
+            severity: Severity::High,
+        });
+    }
+    None
+}
+
+pub fn environment_mismatch(log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
+    let side = |environment: &str| {
+        if environment == "CLIENT" {
+            Side::Client
+        } else {
+            Side::Server
+        }
+    };
+
+    // Dependency errors name the side the mod needs, class loading errors the side the game runs on
+    let (culprit, running) = if let Some(captures) = grab_all!(
+        log,
+        r"Mod '([^']+)' \(\S+\) \S+ requires [^\n]+? on the (CLIENT|SERVER) environment"
+    ) {
+        let needed = side(captures.get(2).expect("Regex err").as_str());
+        (captures.get(1).expect("Regex err").as_str(), needed.other())
+    } else if let Some(captures) = grab_all!(
+        log,
+        r"Cannot load class (\S+) in environment type (CLIENT|SERVER)",
+        r"Attempted to load class (\S+) for invalid dist (CLIENT|DEDICATED_SERVER)"
+    ) {
+        let running = side(captures.get(2).expect("Regex err").as_str());
+        (captures.get(1).expect("Regex err").as_str(), running)
+    } else {
+        return None;
+    };
+    let running = ctx.side.unwrap_or(running);
+
+    let advice = match running {
+        Side::Server => "Client-only mods, like ones that only change rendering or menus, should be removed from the server.",
+        Side::Client => "Server-only mods should be removed from the client.",
+    };
+    Some(CheckReport {
+        title: "Mod on the wrong side".to_string(),
+        description: format!(
+            "`{culprit}` is meant for the {} only, so it can't run on the {running}. {advice}",
+            running.other()
+        ),
+        severity: Severity::High,
+    })
+}
+
+pub fn exit_code(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
+    let code = grab!(
+        log,
+        r"Process exited with code (-?\d+)",
+        r"Process crashed with exit ?code (-?\d+)",
+        r"Game crashed! Exit code: (-?\d+)"
+    )??;
+    if code == "0" {
+        return None;
+    }
+    Some(CheckReport {
+        title: "Abnormal exit code".to_string(),
+        description: format!("The game exited with code `{code}` without an error in the log. This usually means the process was killed, often by the operating system running out of memory. Try allocating less memory or closing other programs, and send the full log if that doesn't help."),
+        severity: Severity::None,
+    })
+}
+
+pub fn disk_full(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
+    let captures = grab_all!(
+        log,
+        r"FileSystemException: ([^\n:]+): No space left on device",
+        r"java\.io\.IOException: No space left on device",
+        r"There is not enough space on the disk"
+    )?;
+    let location = captures
+        .get(1)
+        .map(|path| format!(" while writing to `{}`", path.as_str().trim()))
+        .unwrap_or_default();
+    Some(CheckReport {
+        title: "Disk full".to_string(),
+        description: format!("The disk ran out of space{location}. Free up some disk space, for example by deleting old worlds, backups or logs, and try again."),
+        severity: Severity::High,
+    })
+}
+
+pub fn optifine(log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
+    // OptiFabric has its own check
+    if ctx.known_mods.iter().any(|m| m.0 .0 == "optifabric")
+        || grab!(log, r"me\.modmuss50\.optifabric").is_some()
+    {
+        return None;
+    }
+    if grab!(log, r"\bat (?:net\.)?optifine\.").is_none() {
+        return None;
+    }
+
+    let advice = match ctx.loader {
+        Some(ModLoader::Forge | ModLoader::NeoForge) => "Many Forge mods don't work with it, consider replacing it with [Embeddium](https://modrinth.com/mod/embeddium) and [Oculus](https://modrinth.com/mod/oculus) for shaders.",
+        Some(ModLoader::Fabric(_) | ModLoader::Quilt(_)) => "Consider replacing it with some of the many available [alternatives](https://lambdaurora.dev/optifine_alternatives/).",
+        None => "Make sure you're using the latest OptiFine version for your game version, and try without shaders.",
+    };
+    Some(CheckReport {
+        title: "OptiFine crash".to_string(),
+        description: format!("The game crashed inside of OptiFine. {advice}"),
+        severity: Severity::High,
+    })
+}
+
+/// The entries below a heading of Fabric Loader's incompatible mod set message
+fn incompatible_mod_set_entries<'a>(log: &'a str, heading: &str) -> Vec<&'a str> {
+    let Some(start) = log.find(heading) else {
+        return vec![];
+    };
+    log[start + heading.len()..]
+        .lines()
+        .skip(1)
+        .map_while(|line| line.trim_start().strip_prefix("- "))
+        .collect()
+}
+
+/// Older loaders list the problems below "Unmet dependency listing:", current ones below "More details:"
+fn incompatible_mod_set_problems(log: &str) -> Vec<&str> {
+    let problems = incompatible_mod_set_entries(log, "Unmet dependency listing:");
+    if !problems.is_empty() {
+        return problems;
+    }
+    incompatible_mod_set_entries(log, "More details:")
+}
+
+pub fn incompatible_mod_set(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
+    grab!(
+        log,
+        r"Mod resolution encountered an incompatible mod set!",
+        r"Some of your mods are incompatible with the game or each other!"
+    )?;
+    let problems = incompatible_mod_set_problems(log);
+    // Current loaders add ", this may resolve your problem:" to the heading
+    let solutions = incompatible_mod_set_entries(log, "A potential solution has been determined");
+    if problems.len() < 2 {
+        return None;
+    }
+
+    // Embed fields are limited to 1024 characters
+    let mut description = String::new();
+    for (i, problem) in problems.iter().enumerate() {
+        let line = format!("- {problem}\n");
+        if description.len() + line.len() > 700 {
+            description.push_str(&format!("- ...and {} more\n", problems.len() - i));
+            break;
+        }
+        description.push_str(&line);
+    }
+    if let Some(solution) = solutions.first()
+        && description.len() + solution.len() < 1000
+    {
+        description.push_str(&format!("**Suggested fix:** {solution}"));
+    }
+
+    Some(CheckReport {
+        title: "Incompatible mod set".to_string(),
+        description,
+        severity: Severity::High,
+    })
+}
+
+pub fn fabric_loader_outdated(log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
+    let captures = grab_all!(
+        log,
+        r"Mod '([^']+)' \(\S+\) \S+ requires version (\S+) or later of (?:mod 'Fabric Loader' \()?fabricloader\b"
+    )?;
+    let dependent = captures.get(1).expect("Regex err").as_str();
+    let required = captures.get(2).expect("Regex err 2").as_str();
+
+    let installed = match &ctx.loader {
+        Some(ModLoader::Fabric(Some(version))) => Some(version),
+        _ => None,
+    };
+    // The requirement may also be an upper bound, which updating wouldn't fix
+    if let Some(installed) = installed
+        && let (Some(installed), Some(required)) =
+            (parse_version(installed), parse_version(required))
+        && installed >= required
+    {
+        return None;
+    }
+
+    Some(CheckReport {
+        title: "Fabric Loader outdated".to_string(),
+        description: format!(
+            "`{dependent}` needs Fabric Loader `{required}` or newer{}. Update Fabric Loader in your launcher, or install the [latest version](https://fabricmc.net/use/installer/).",
+            installed.map(|version| format!(", but `{version}` is installed")).unwrap_or_default()
+        ),
+        severity: Severity::High,
+    })
+}
+
+pub fn connection_refused(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
+    // Only the connect exception means the server couldn't be reached, netty logs plenty of other noise
+    let captures = grab_all!(
+        log,
+        r"io\.netty\.channel\.AbstractChannel\$AnnotatedConnectException: Connection refused(?:: (?:no further information: )?(\S+))?",
+        r"io\.netty\.channel\.ConnectTimeoutException: connection timed out(?:: (\S+))?"
+    )?;
+    let address = captures
+        .get(1)
+        .map(|address| format!(" at `{}`", address.as_str()))
+        .unwrap_or_default();
+    Some(CheckReport {
+        title: "Server unreachable".to_string(),
+        description: format!("The game couldn't connect to the server{address}. This isn't a crash: check that the server is running, the IP and port are correct, and that no firewall is blocking the connection."),
+        severity: Severity::Medium,
+    })
+}
+
+pub fn auth_servers_down(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
+    if grab!(
+        log,
+        r"Authentication servers are down",
+        r"Couldn't connect to authentication servers",
+        r"AuthenticationUnavailableException"
+    )
+    .is_some()
+    {
+        return Some(CheckReport {
+            title: "Authentication servers unreachable".to_string(),
+            description: "Mojang's authentication servers couldn't be reached. This is usually temporary, check the [Minecraft status](https://status.mojang.com/) or your internet connection and try again later.".to_string(),
+            severity: Severity::Medium,
+        });
+    }
+    None
+}
+
+pub fn invalid_session(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
+    if grab!(
+        log,
+        r"Failed to verify username",
+        r"Invalid session \(Try restarting your game",
+        r"InvalidCredentialsException",
+        r"multiplayer\.disconnect\.(?:invalid_session|unverified_username)"
+    )
+    .is_some()
+    {
+        return Some(CheckReport {
+            title: "Invalid session".to_string(),
+            description: "Your login session is invalid or expired. Log out of your account in the launcher and log back in, then restart the game.".to_string(),
+            severity: Severity::Medium,
+        });
+    }
+    None
+}
+
+pub fn world_corruption(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
+    // Only errors while loading chunks, NBT warnings alone don't mean that the world is broken
+    let captures = grab_all!(
+        log,
+        r"(?:Couldn't|Failed to) (?:load|read) chunk \[(-?\d+), (-?\d+)\]",
+        r"Failed to read chunk",
+        r"at net\.minecraft\.\S+\.Chunk(?:Serializer|Deserializer)\.(?:read|deserialize)",
+        r"Exception reading [^\n]*(r\.-?\d+\.-?\d+\.mca)"
+    )?;
+    let location = match (captures.get(1), captures.get(2)) {
+        (Some(x), Some(z)) => format!(
+            " The chunk at `{}, {}` is affected.",
+            x.as_str(),
+            z.as_str()
+        ),
+        (Some(region), None) => format!(" The region file `{}` is affected.", region.as_str()),
+        _ => String::new(),
+    };
+    Some(CheckReport {
+        title: "World corruption".to_string(),
+        description: format!("Part of the world save couldn't be loaded, so it may be corrupted.{location} Restore the world from a backup if you have one, and make sure the game isn't closed while it's saving."),
+        severity: Severity::High,
+    })
+}
+
+pub fn shader_compilation(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
+    // A shader that doesn't compile is the pack's fault, unlike a driver that can't create a context
+    let shader = grab_all!(
+        log,
+        r"(?:net\.irisshaders|net\.coderbot)\.iris\.gl\.shader\.ShaderCompileException: ([^\s:]+)",
+        r"ShaderCompileException: ([^\s:]+)",
+        r"[Ff]ailed to compile (?:\w+ )?shader[^\n]*?(\S+\.(?:[fvg]sh|glsl))"
+    )?
+    .get(1)
+    .map(|shader| shader.as_str().to_string());
+    let pack = grab!(log, r"Using shaderpack: ([^\r\n]+)").flatten();
+
+    let culprit = match (shader, pack) {
+        (Some(shader), Some(pack)) => {
+            format!("The shader `{shader}` of the shader pack `{}`", pack.trim())
+        }
+        (None, Some(pack)) => format!("The shader pack `{}`", pack.trim()),
+        (Some(shader), None) => format!("The shader `{shader}`"),
+        (None, None) => "A shader".to_string(),
+    };
+    Some(CheckReport {
+        title: "Shader compilation failed".to_string(),
+        description: format!("{culprit} failed to compile. Update the shader pack or try a different one, shader packs often don't support every GPU or Iris version."),
+        severity: Severity::Medium,
+    })
+}
+
+/// Package prefix, name and Modrinth page of cross-loader libraries that break when mismatched
+const LIBRARIES: &[(&str, &str, &str)] = &[
+    (
+        "dev.architectury.",
+        "Architectury API",
+        "https://modrinth.com/mod/architectury-api",
+    ),
+    (
+        "me.shedaniel.clothconfig",
+        "Cloth Config",
+        "https://modrinth.com/mod/cloth-config",
+    ),
+    (
+        "fuzs.forgeconfigapiport.",
+        "Forge Config API Port",
+        "https://modrinth.com/mod/forge-config-api-port",
+    ),
+    (
+        "dev.isxander.yacl",
+        "YetAnotherConfigLib",
+        "https://modrinth.com/mod/yacl",
+    ),
+    (
+        "com.terraformersmc.modmenu.",
+        "Mod Menu",
+        "https://modrinth.com/mod/modmenu",
+    ),
+    (
+        "io.wispforest.owo.",
+        "oωo (owo-lib)",
+        "https://modrinth.com/mod/owo-lib",
+    ),
+];
+
+pub fn library_mismatch(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
+    let missing = grab!(
+        log,
+        r"java\.lang\.(?:NoSuchMethodError|NoClassDefFoundError):? ([^\r\n]+)"
+    )??
+    .replace('/', ".");
+    let (_, name, url) = LIBRARIES
+        .iter()
+        .find(|(package, _, _)| missing.contains(package))?;
+
+    Some(CheckReport {
+        title: "Library version mismatch".to_string(),
+        description: format!(
+            "A mod tried to use something from {name} that doesn't exist in the installed version. Update [{name}]({url}) to the version your mods need."
+        ),
+        severity: Severity::High,
+    })
+}
+
+/// Stack frames from these packages never belong to the mod that made the call
+const PLATFORM_PACKAGES: &[&str] = &[
+    "java.",
+    "jdk.",
+    "sun.",
+    "net.minecraft.",
+    "com.mojang.",
+    "net.fabricmc.",
+    "org.quiltmc.",
+    "net.minecraftforge.",
+    "net.neoforged.",
+    "cpw.mods.",
+    "org.spongepowered.",
+];
+
+/// The jar or package of the first stack frame outside of the game and loader
+fn calling_mod(frames: &str, ctx: &EnvironmentContext) -> Option<String> {
+    let frame = frames
+        .lines()
+        .skip(1)
+        .take(20)
+        .filter_map(|line| line.trim_start().strip_prefix("at "))
+        .find(|frame| {
+            !PLATFORM_PACKAGES
+                .iter()
+                .any(|package| frame.starts_with(package))
+        })?;
+
+    if let Some(jar) = grab!(frame, r"\[([^\]\s%:]+\.jar)").flatten() {
+        return Some(jar);
+    }
+    let class = frame.split('(').next()?;
+    if let Some(id) = ctx.attribute_package(class) {
+        return Some(id);
+    }
+    Some(class.rsplitn(3, '.').last()?.to_string())
+}
+
+pub fn api_mismatch(log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
+    // Known libraries get a more specific report
+    if library_mismatch(log, ctx).is_some() || fabric_api_mismatch(log, ctx).is_some() {
+        return None;
+    }
+    let captures = grab_all!(
+        log,
+        r"java\.lang\.(NoSuchMethodError|NoClassDefFoundError):? ([^\r\n]+)"
+    )?;
+    let kind = if captures.get(1).expect("Regex err").as_str() == "NoSuchMethodError" {
+        "method"
+    } else {
+        "class"
+    };
+    let missing = captures
+        .get(2)
+        .expect("Regex err 2")
+        .as_str()
+        .trim()
+        .trim_matches('\'')
+        .replace('/', ".");
+    let caller = calling_mod(&log[captures.get(0).expect("Regex err 3").end()..], ctx)
+        .map(|caller| format!(" `{caller}` tried to use it, so check that it matches the versions of your other mods."))
+        .unwrap_or_default();
+
+    Some(CheckReport {
+        title: "Missing method or class".to_string(),
+        description: format!(
+            "The {kind} `{missing}` doesn't exist, which usually means two mods have mismatching versions, or a mod is made for a different Minecraft version.{caller}"
+        ),
+        severity: Severity::Medium,
+    })
+}
+
+pub fn download_failed(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
+    // The game also fails to download things like skins while running, which is harmless
+    if game_started(log) {
+        return None;
+    }
+    let file = grab!(
+        log,
+        r"Failed to download (?:file )?(\S+\.(?:jar|json|zip)|https?://\S+)",
+        r"Couldn't download (?:file )?(\S+\.(?:jar|json|zip)|https?://\S+)",
+        r"Download of (\S+) failed"
+    )?;
+    let file = file
+        .map(|file| format!(" `{}`", file.trim_end_matches([',', '.', ':'])))
+        .unwrap_or_default();
+    Some(CheckReport {
+        title: "Download failed".to_string(),
+        description: format!("The launcher couldn't download{file} before starting the game, so this isn't a crash of the game itself. Check your internet connection and try again, or switch the download mirror in your launcher's settings if it has one."),
+        severity: Severity::Medium,
+    })
+}
+
+pub fn ssl_handshake(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
+    if grab!(
+        log,
+        r"javax\.net\.ssl\.SSLHandshakeException",
+        r"PKIX path building failed",
+        r"unable to find valid certification path to requested target"
+    )
+    .is_some()
+    {
+        return Some(CheckReport {
+            title: "Secure connection failed".to_string(),
+            description: "A secure connection couldn't be established. This is often caused by a wrong date or time on your computer, a proxy or antivirus intercepting connections, or an outdated Java version. Check your system clock and try without the proxy or antivirus web protection.".to_string(),
+            severity: Severity::Medium,
+        });
+    }
+    None
+}
+
+pub fn java_path(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
+    let captures = grab_all!(
+        log,
+        r"Cannot run program \x22([^\x22]*java[^\x22]*)\x22",
+        r"Unable to launch[^\n]*?(\S*javaw?(?:\.exe)?)\b",
+        r"The Java binary \x22([^\x22]+)\x22 couldn't be found",
+        r"Java (?:path|executable) \x22?([^\x22\n]+?)\x22? (?:is not valid|does not exist)"
+    )?;
+    let path = captures.get(1).expect("Regex err").as_str();
+    Some(CheckReport {
+        title: "Invalid Java path".to_string(),
+        description: format!("The launcher couldn't start Java from `{path}`, so the game never started. Select a valid Java installation in your launcher's settings, or let the launcher download one automatically."),
+        severity: Severity::High,
+    })
+}
+
+pub fn suspected_mods(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
+    let start = crate::cached_regex!(r"Suspected Mods?:").find(log)?.end();
+
+    // Either listed on the same line or on the following indented lines
+    let mut section = String::new();
+    for (i, line) in log[start..].lines().enumerate() {
+        if i > 0 && !line.starts_with(['\t', ' ']) {
+            break;
+        }
+        // Issue tracker URLs and stack frames are indented further
+        if i == 0 || !line.trim_start_matches(' ').starts_with("\t\t") {
+            section.push_str(line);
+            section.push('\n');
+        }
+    }
+
+    let mut mods: Vec<String> = vec![];
+    for captures in crate::cached_regex!(r"([^,\n\t]+?) \(([\w\-]+)\)").captures_iter(&section) {
+        let name = captures.get(1).expect("Regex err").as_str().trim();
+        let id = captures.get(2).expect("Regex err 2").as_str();
+        let entry = format!("- {name} (`{id}`)");
+        if !mods.contains(&entry) {
+            mods.push(entry);
+        }
+        if mods.len() == 10 {
+            break;
+        }
+    }
+    if mods.is_empty() {
+        return None;
+    }
+
+    Some(CheckReport {
+        title: "Suspected mods".to_string(),
+        description: format!(
+            "The crash report names these mods as likely causes:\n{}",
+            mods.join("\n")
+        ),
+        severity: Severity::None,
+    })
+}
+
+pub fn sodium_version_mismatch(_log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
+    let sodium = ctx.known_mods.iter().find(|m| m.0 .0 == "sodium")?;
+    // Only versions that name their Minecraft version, like `0.5.3+mc1.20.1` or `mc1.19.2-0.4.4`
+    let sodium_minecraft = grab!(&sodium.1, r"mc(\d+\.\d+(?:\.\d+)?)").flatten()?;
+    let game = parse_version(ctx.mc_version.as_ref()?)?;
+    let target = parse_version(&sodium_minecraft)?;
+
+    if !same_version(&game, &target) {
+        return Some(CheckReport {
+            title: "Sodium version mismatch".to_string(),
+            description: format!(
+                "The installed Sodium `{}` is made for Minecraft `{sodium_minecraft}`, but the game is running `{}`. Download the matching version of [Sodium](https://modrinth.com/mod/sodium/versions) from Modrinth.",
+                sodium.1,
+                ctx.mc_version.as_ref()?
+            ),
+            severity: Severity::High,
+        });
+    }
+    None
+}
+
+pub fn large_modpack(
+    _log: &str,
+    ctx: &EnvironmentContext,
+    settings: &CheckSettings,
+) -> Option<CheckReport> {
+    let count = ctx.mods.len();
+    if count <= settings.large_modpack_threshold {
+        return None;
+    }
+    let recommended = count as u64 * settings.memory_per_mod_mb;
+    if ctx
+        .max_memory_mb
+        .is_some_and(|memory| memory >= recommended)
+    {
+        return None;
+    }
+
+    let allocated = ctx
+        .max_memory_mb
+        .map(|memory| format!(" Currently `{memory}` MiB are allocated."))
+        .unwrap_or_default();
+    Some(CheckReport {
+        title: "Large modpack".to_string(),
+        description: format!("`{count}` mods are installed, which needs a lot of memory. If the game crashes or freezes, try allocating around `{}` GiB of memory in your launcher, but not more than your computer has.{allocated}", recommended.div_ceil(1024)),
+        severity: Severity::Medium,
+    })
+}
+
+pub fn memory_exceeds_system(_log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
+    let (Some(allocated), Some(system)) = (ctx.max_memory_mb, ctx.system_memory_mb) else {
+        return None;
+    };
+    if allocated <= system {
+        return None;
+    }
+    Some(CheckReport {
+        title: "Too much memory allocated".to_string(),
+        description: format!("`{allocated}` MiB of memory are allocated to the game, but the system only has `{system}` MiB, which can stop Java from starting or make the whole system swap. Allocate at most half of your system's memory."),
+        severity: Severity::Medium,
+    })
+}
+
+pub fn gc_flags(log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
+    if ctx.side != Some(Side::Server) || !ctx.max_memory_mb.is_some_and(|memory| memory >= 6144) {
+        return None;
+    }
+    // Without the arguments in the log, there's no telling whether GC flags are set
+    if grab!(log, r"-Xmx\d+").is_none()
+        || grab!(log, r"-XX:\+Use(?:G1|Z|Shenandoah|Parallel)GC").is_some()
+    {
+        return None;
+    }
+    Some(CheckReport {
+        title: "No garbage collector flags".to_string(),
+        description: "This server has a lot of memory allocated but no garbage collector flags set, which can cause lag spikes. Consider using [Aikar's flags](https://docs.papermc.io/paper/aikars-flags) to tune it.".to_string(),
+        severity: Severity::None,
+    })
+}
+
+pub fn refmap_missing(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
+    let captures = grab_all!(
+        log,
+        r"Reference map '([^']+)' for (\S+?\.json) could not be read"
+    )?;
+    let refmap = captures.get(1).expect("Regex err").as_str();
+    let config = captures.get(2).expect("Regex err").as_str();
+    Some(CheckReport {
+        title: "Mixin refmap missing".to_string(),
+        description: format!("The mixin config `{config}` is missing its reference map `{refmap}`. This is not your fault, the mod providing it was most likely built incorrectly, so its mixins may fail to apply. Try a different version of that mod or report it to its author."),
+        severity: Severity::Medium,
+    })
+}
+
+/// A single lag spike is normal, e.g. while the world is loading
+const SERVER_OVERLOADED_THRESHOLD: usize = 5;
+
+pub fn server_overloaded(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
+    let behind: Vec<u64> =
+        crate::cached_regex!(r"Can't keep up! Is the server overloaded\? Running (\d+)ms")
+            .captures_iter(log)
+            .filter_map(|captures| captures[1].parse().ok())
+            .collect();
+    if behind.len() < SERVER_OVERLOADED_THRESHOLD {
+        return None;
+    }
+    let worst = behind.iter().max().copied().unwrap_or_default();
+    Some(CheckReport {
+        title: "Server overloaded".to_string(),
+        description: format!("The server couldn't keep up `{}` times and fell up to `{worst}` ms behind, so it's lagging. Performance mods like [Lithium](https://modrinth.com/mod/lithium) can help, otherwise the hardware might not be enough for this world or modpack.", behind.len()),
+        severity: Severity::Medium,
+    })
+}
+
+pub fn server_watchdog(log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
+    let captures = grab_all!(
+        log,
+        r"A single server tick took ([\d.]+) seconds",
+        r"ServerHangWatchdog detected",
+        r"Considering it to be crashed"
+    )?;
+    let seconds = captures
+        .get(1)
+        .map(|seconds| format!(" A single tick took `{}` seconds.", seconds.as_str()))
+        .unwrap_or_default();
+    // The thread dump shows what the server thread was stuck on
+    let culprit = crate::cached_regex!(r#""Server thread"[^\n]*"#)
+        .find(log)
+        .and_then(|thread| calling_mod(&log[thread.start()..], ctx))
+        .map(|culprit| {
+            format!(" The server thread was stuck in `{culprit}`, which is likely the cause.")
+        })
+        .unwrap_or_default();
+    Some(CheckReport {
+        title: "Server watchdog crash".to_string(),
+        description: format!("The server stopped responding, so the watchdog forcibly shut it down.{seconds}{culprit} This is usually a mod stuck in a loop or deadlock, or a world that's too heavy to tick."),
+        severity: Severity::High,
+    })
+}
+
+pub fn forge_dependency(log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
+    if !matches!(ctx.loader, Some(ModLoader::Forge | ModLoader::NeoForge)) {
+        return None;
+    }
+    let mut problems: Vec<String> = crate::cached_regex!(
+        r"Mod ID: '([^']+)', Requested by: '([^']+)', Expected range: '([^']+)', Actual version: '([^']+)'"
+    )
+    .captures_iter(log)
+    // A NeoForge version mismatch is reported by neoforge_incompatible
+    .filter(|captures| &captures[1] != "neoforge")
+    .map(|captures| {
+        let actual = if &captures[4] == "[MISSING]" {
+            "is missing".to_string()
+        } else {
+            format!("is `{}`", &captures[4])
+        };
+        format!(
+            "- `{}` needs `{}` in the range `{}`, but it {actual}",
+            &captures[2], &captures[1], &captures[3]
+        )
+    })
+    .collect();
+    // Crash reports word it differently
+    if problems.is_empty() {
+        problems =
+            crate::cached_regex!(r"Failure message: Mod (\S+) requires (\S+) (\S+) or above")
+                .captures_iter(log)
+                .map(|captures| {
+                    format!(
+                        "- `{}` needs `{}` version `{}` or newer",
+                        &captures[1], &captures[2], &captures[3]
+                    )
+                })
+                .collect();
+    }
+    if problems.is_empty() {
+        return None;
+    }
+    // The same listing is often logged more than once
+    problems.sort();
+    problems.dedup();
+    problems.truncate(10);
+
+    Some(CheckReport {
+        title: "Missing or unsupported dependencies".to_string(),
+        description: format!(
+            "Some mods need other mods that are missing or have the wrong version:\n{}\nInstall or update the dependencies to a matching version.",
+            problems.join("\n")
+        ),
+        severity: Severity::High,
+    })
+}
+
+pub fn neoforge_incompatible(log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
+    if !matches!(ctx.loader, Some(ModLoader::NeoForge)) {
+        return None;
+    }
+    let captures = grab_all!(
+        log,
+        r"Mod ID: 'neoforge', Requested by: '([^']+)', Expected range: '([^']+)'",
+        r"Mod (\S+) requires neoforge (\S+ or above)",
+        r"Mod file (\S+) needs language provider \S+:(\S+) to load"
+    )?;
+    let mod_name = captures.get(1).expect("Regex err").as_str();
+    let range = captures.get(2).expect("Regex err 2").as_str();
+    Some(CheckReport {
+        title: "Mod incompatible with NeoForge version".to_string(),
+        description: format!("`{mod_name}` needs NeoForge `{range}`, which doesn't match the installed version. Update [NeoForge](https://neoforged.net/) in your launcher, or get a version of the mod made for your NeoForge version from its [Modrinth](https://modrinth.com/mods?g=categories:neoforge) or CurseForge page."),
+        severity: Severity::High,
+    })
+}
+
+/// Mods with recurring crashes, as the name, a substring of their crashes and the advice
+const KNOWN_MOD_CRASHES: &[(&str, &str, &str)] = &[
+    (
+        "Essential",
+        "at gg.essential.",
+        "Essential often breaks with other mods or after updates. Update it, or remove it to see if the crash goes away.",
+    ),
+    (
+        "Feather",
+        "at net.digitalingot.feather",
+        "Feather Client modifies the game heavily and is incompatible with many mods. Try launching without it, using a regular launcher.",
+    ),
+];
+
+pub fn known_mod_crash(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
+    let (name, _, advice) = KNOWN_MOD_CRASHES
+        .iter()
+        .find(|(_, signature, _)| log.contains(signature))?;
+    Some(CheckReport {
+        title: format!("{name} crash"),
+        description: format!("The crash involves {name}. {advice}"),
+        severity: Severity::Medium,
+    })
+}
+
+pub fn mixin_conflict(log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
+    let (first, second) = if let Some(captures) = grab_all!(
+        log,
+        r"@(?:Redirect|ModifyConstant) conflict\. Skipping \S+ from mod (\S+?)->\S+ with priority \d+, already (?:redirected|modified) by \S+ from mod (\S+?)->"
+    ) {
+        (captures[1].to_string(), captures[2].to_string())
+    } else {
+        let captures = grab_all!(
+            log,
+            r"(?:@Overwrite|Method overwrite) conflict for \S+ in \S+ from mod (\S+?),? previously written by ([\w.$]+)"
+        )?;
+        let class = &captures[2];
+        let owner = ctx
+            .attribute_package(class)
+            .unwrap_or_else(|| class.rsplitn(2, '.').last().unwrap_or(class).to_string());
+        (captures[1].to_string(), owner)
+    };
+    if first == second {
+        return None;
+    }
+    Some(CheckReport {
+        title: "Mixin conflict".to_string(),
+        description: format!("`{first}` and `{second}` both change the same part of the game and conflict with each other. Check if they are known to be incompatible or if there are updated versions, otherwise remove one of them."),
+        severity: Severity::Medium,
+    })
+}
+
+pub fn shaders_without_loader(log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
+    let shader_loaders = ["iris", "oculus", "optifabric"];
+    if ctx
+        .mods
+        .iter()
+        .any(|(id, _)| shader_loaders.contains(&id.as_str()))
+        || ctx
+            .known_mods
+            .iter()
+            .any(|known| shader_loaders.contains(&known.0 .0))
+        || log.contains("OptiFine")
+    {
+        return None;
+    }
+    // Shader packs put into the resource pack folder have no pack metadata
+    let pack = grab!(
+        log,
+        r"(?i)Missing metadata in pack file/(\S*(?:shader|bsl|complementary|seus|sildur|makeup|solas)\S*)",
+        r"(?i)Invalid pack \S*?(\S*(?:shader|bsl|complementary|seus|sildur|makeup|solas)\S*)"
+    )?
+    .map(|pack| format!(" `{pack}` looks like a shader pack, but shader packs aren't resource packs."))
+    .unwrap_or_default();
+    Some(CheckReport {
+        title: "Shaders without a shader loader".to_string(),
+        description: format!("Shader packs need a mod to load them.{pack} Install [Iris](https://modrinth.com/mod/iris) and put the shader pack into the `shaderpacks` folder instead."),
+        severity: Severity::Medium,
+    })
+}
+
+/// Whether the log has lines from the game itself, rather than only the launcher's
+fn game_started(log: &str) -> bool {
+    grab!(
+        log,
+        r"Loading Minecraft \S+ with",
+        r"---- Minecraft Crash Report ----",
+        r"\[[^\]]*(?:main|Render thread|Server thread)/(?:INFO|WARN|ERROR)\]"
+    )
+    .is_some()
+}
+
+pub fn launcher_log(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
+    if game_started(log) {
+        return None;
+    }
+    // Prism and MultiMC log their source files, the vanilla launcher its own timestamp format
+    grab!(
+        log,
+        r"\b(?:Launcher|Application|LaunchController)\.cpp:\d+",
+        r"(?m)^\s*\d+\.\d+ [DIWC] \| ",
+        r"\[(?:Info|Warning|Error): \d{4}-\d{2}-\d{2} [\d:.]+: "
+    )?;
+    Some(CheckReport {
+        title: "Launcher log".to_string(),
+        description: "This looks like a log of the launcher, not of the game. Please send the game log instead, you can find it as `latest.log` in the `logs` folder of your instance or `.minecraft` folder.".to_string(),
+        severity: Severity::None,
+    })
+}
+
+pub fn fabric_api_mismatch(log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
+    let captures = grab_all!(
+        log,
+        r"java\.lang\.NoSuchMethodError:? '?([^'\r\n]*net[./]fabricmc[./]fabric[./]api[^'\r\n]*)'?"
+    )?;
+    let method = captures
+        .get(1)
+        .expect("Regex err")
+        .as_str()
+        .trim()
+        .replace('/', ".");
+    let caller = calling_mod(&log[captures.get(0).expect("Regex err 2").end()..], ctx)
+        .map(|caller| format!(" It was called by `{caller}`."))
+        .unwrap_or_default();
+    let installed = ctx
+        .mods
+        .iter()
+        .find(|(id, _)| id == "fabric-api" || id == "fabric")
+        .map(|(_, version)| format!(" The installed Fabric API version is `{version}`."))
+        .unwrap_or_default();
+    Some(CheckReport {
+        title: "Fabric API method missing".to_string(),
+        description: format!("The Fabric API method `{method}` doesn't exist in the installed version.{caller}{installed} Either the mod is outdated and uses something that was removed, or Fabric API is older than the mod needs. Update both the mod and [Fabric API](https://modrinth.com/mod/fabric-api) to the latest versions for your Minecraft version."),
+        severity: Severity::High,
+    })
+}
+
+/// The pack and file named by a resource or data pack error, if the message has them
+fn pack_location(captures: &regex::Captures) -> String {
+    let file = captures.name("file").map(|file| file.as_str());
+    let pack = captures.name("pack").map(|pack| pack.as_str());
+    match (file, pack) {
+        (Some(file), Some(pack)) => format!(" The file `{file}` in `{pack}` is broken."),
+        (Some(file), None) => format!(" The file `{file}` is broken."),
+        (None, Some(pack)) => format!(" The pack `{pack}` is broken."),
+        (None, None) => String::new(),
+    }
+}
+
+pub fn resource_pack_error(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
+    let captures = grab_all!(
+        log,
+        r"Exception loading blockstate definition: '(?P<file>[^']+)' in resourcepack: '(?P<pack>[^']+)'",
+        r"Unable to parse metadata section of resourcepack: (?P<pack>\S+)",
+        r"Caught error loading resourcepacks, removing all selected resourcepacks"
+    )?;
+    Some(CheckReport {
+        title: "Broken resource pack".to_string(),
+        description: format!("A resource pack couldn't be loaded.{} Remove the pack or fix it, it might also be made for a different Minecraft version.", pack_location(&captures)),
+        severity: Severity::Medium,
+    })
+}
+
+pub fn datapack_error(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
+    let captures = grab_all!(
+        log,
+        r"Couldn't parse data file (?P<file>\S+) from (?P<pack>[^\s:]+)",
+        r"Couldn't load tag (?P<file>\S+) as it is missing following references",
+        r"Parsing error loading recipe (?P<file>\S+)",
+        r"Failed to load datapacks, can't proceed with server load"
+    )?;
+    Some(CheckReport {
+        title: "Broken data pack".to_string(),
+        description: format!("A data pack of the world couldn't be loaded.{} Remove it from the `datapacks` folder of the world or fix it, it might also be made for a different Minecraft version or need a mod that's missing.", pack_location(&captures)),
+        severity: Severity::Medium,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log_checking::environment::get_environment_info;
+
+    #[test]
+    fn same_version_pads_with_zeros() {
+        assert!(same_version(&[1, 20], &[1, 20, 0]));
+        assert!(!same_version(&[1, 20, 1], &[1, 20, 2]));
+    }
+
+    #[test]
+    fn fabric_api_outdated_on_newer_minecraft() {
+        let log = "Loading Minecraft 1.20.4 with Fabric Loader 0.15.11\nLoading 2 mods:\n\t- fabric-api 0.92.2+1.20.1\n\t- minecraft 1.20.4\n";
+        let report = fabric_api_outdated(log, &get_environment_info(log)).expect("No report");
+        assert!(report.description.contains("made for Minecraft `1.20.1`"));
+
+        let log = "Loading Minecraft 1.20.1 with Fabric Loader 0.15.11\nLoading 2 mods:\n\t- fabric-api 0.92.2+1.20.1\n\t- minecraft 1.20.1\n";
+        assert!(fabric_api_outdated(log, &get_environment_info(log)).is_none());
+    }
+
+    #[test]
+    fn out_of_memory_compares_the_allocation() {
+        let log = "-Xmx2G\njava.lang.OutOfMemoryError: Java heap space\n";
+        let report = out_of_memory(log, &get_environment_info(log)).expect("No report");
+        assert!(report.description.contains("(`2048` MiB)"));
+
+        let log = "-Xmx8G\njava.lang.OutOfMemoryError: Java heap space\n";
+        let report = out_of_memory(log, &get_environment_info(log)).expect("No report");
+        assert!(report.description.contains("leaking memory"));
+    }
+
+    #[test]
+    fn out_of_metaspace_is_not_about_heap() {
+        let log = "java.lang.OutOfMemoryError: Metaspace\n";
+        let report = out_of_memory(log, &get_environment_info(log)).expect("No report");
+        assert_eq!(report.title, "Out of metaspace");
+    }
+
+    #[test]
+    fn large_modpack_uses_the_configured_memory_per_mod() {
+        let mods: String = (0..500).map(|i| format!("\t- mod{i} 1.0.0\n")).collect();
+        let log = format!("-Xmx6G\nLoading 500 mods:\n{mods}");
+        let ctx = get_environment_info(&log);
+
+        let report = large_modpack(&log, &ctx, &CheckSettings::default()).expect("No report");
+        assert!(report.description.contains("`500` mods"));
+        assert!(report.description.contains("around `8` GiB"));
+
+        let settings = CheckSettings {
+            memory_per_mod_mb: 12,
+            ..CheckSettings::default()
+        };
+        assert!(large_modpack(&log, &ctx, &settings).is_none());
+    }
+
+    #[test]
+    fn minecraft_version_mismatch_reports_the_loaded_version() {
+        let log = "Mod 'Sodium' (sodium) 0.5.3 requires version 1.20.1 of 'Minecraft' (minecraft), but only the wrong version is present: 1.20.4!\n";
+        let report =
+            minecraft_version_mismatch(log, &get_environment_info(log)).expect("No report");
+        assert!(report
+            .description
+            .contains("`Sodium` mod requires version 1.20.1"));
+        assert!(report.description.contains("running `1.20.4`"));
+        assert!(!report.description.contains("also mentions"));
+
+        let log = format!("Loading Minecraft 1.20.2 with Fabric Loader 0.15.11\n{log}");
+        let report =
+            minecraft_version_mismatch(&log, &get_environment_info(&log)).expect("No report");
+        assert!(report.description.contains("running `1.20.4`"));
+        assert!(report
+            .description
+            .contains("The log also mentions Minecraft `1.20.2`."));
+    }
+
+    #[test]
+    fn disabled_checks_are_skipped() {
+        let log = "Failed to verify username!";
+        let ctx = get_environment_info(log);
+        let ran = |settings: &CheckSettings| {
+            check_checks(log, &ctx, settings)
+                .iter()
+                .any(|(id, _)| *id == "invalid_session")
+        };
+        assert!(ran(&CheckSettings::default()));
+        assert!(!ran(&CheckSettings {
+            disabled_checks: vec!["invalid_session".to_string()],
+            ..CheckSettings::default()
+        }));
+    }
+
+    #[test]
+    fn sodium_iris_names_both_versions() {
+        let log = "Loading 2 mods:\n\t- iris 1.6.11\n\t- sodium 0.5.3\nMod 'Iris' (iris) 1.6.11 requires version 0.5.8 or later of mod 'Sodium' (sodium), but only the wrong version is present: 0.5.3!\n";
+        let report = sodium_iris(log, &get_environment_info(log)).expect("No report");
+        assert!(report
+            .description
+            .contains("Sodium `0.5.3` and Iris `1.6.11`"));
+
+        let log = "Loading 2 mods:\n\t- iris 1.6.11\n\t- sodium 0.5.3\n";
+        assert!(sodium_iris(log, &get_environment_info(log)).is_none());
+    }
+
+    #[test]
+    fn missing_field_ignores_forge() {
+        let log = "--fml.forgeVersion, 47.2.0\njava.lang.NoSuchFieldError: field_1234";
+        assert!(missing_field(log, &get_environment_info(log)).is_none());
+
+        let log = "Loading Minecraft 1.20.1 with Fabric Loader 0.15.11\njava.lang.NoSuchFieldError: field_1234";
+        assert!(missing_field(log, &get_environment_info(log)).is_some());
+    }
+
+    #[test]
+    fn quilt_missing_dependency() {
+        let log = "Loading Minecraft 1.20.1 with Quilt Loader 0.26.0\nMod 'Mod Menu' (modmenu) 7.2.2 requires any version of mod 'Quilted Fabric API' (quilted_fabric_api), which is missing!\n";
+        let report = dependency_generic(log, &get_environment_info(log)).expect("No report");
+        assert!(report.description.contains("`Mod Menu`"));
+        assert!(report.description.contains("Quilted Fabric API"));
+    }
+
+    #[test]
+    fn rosetta_only_for_x86_java_on_apple_silicon() {
+        let log = "Operating System: Mac OS X (aarch64) version 14.1\nJava is version 17.0.8, using 64 (x86_64) architecture, from Eclipse Adoptium.\n";
+        assert!(rosetta(log, &get_environment_info(log)).is_some());
+
+        let log = "Operating System: Mac OS X (aarch64) version 14.1\nJava is version 17.0.8, using 64 (aarch64) architecture, from Eclipse Adoptium.\n";
+        assert!(rosetta(log, &get_environment_info(log)).is_none());
+    }
+
+    #[test]
+    fn exit_code_is_informational() {
+        let log = "Process exited with code -1073740791";
+        let report = exit_code(log, &get_environment_info(log)).expect("No report");
+        assert_eq!(report.severity, Severity::None);
+
+        let log = "Process exited with code 0";
+        assert!(exit_code(log, &get_environment_info(log)).is_none());
+    }
+
+    #[test]
+    fn optifine_shader_crash_on_forge() {
+        let log = "--fml.forgeVersion, 47.2.0\njava.lang.NullPointerException\n\tat net.optifine.shaders.Shaders.init(Shaders.java:100)\n";
+        let report = optifine(log, &get_environment_info(log)).expect("No report");
+        assert!(report.description.contains("Embeddium"));
+    }
+
+    const CURRENT_INCOMPATIBLE_MOD_SET: &str = "\
+[main/ERROR]: Incompatible mods found!
+net.fabricmc.loader.impl.FormattedException: Some of your mods are incompatible with the game or each other!
+A potential solution has been determined, this may resolve your problem:
+\t - Install fabric-api, version 0.92.0 or later.
+More details:
+\t - Mod 'Sodium' (sodium) 0.5.3 requires version 0.92.0 or later of mod 'Fabric API' (fabric-api), which is missing!
+\t - Mod 'Iris' (iris) 1.6.11 requires any version of mod 'Sodium' (sodium), which is missing!
+\tat net.fabricmc.loader.impl.FormattedException.ofLocalized(FormattedException.java:51)
+";
+
+    #[test]
+    fn incompatible_mod_set_current_loader() {
+        let log = CURRENT_INCOMPATIBLE_MOD_SET;
+        let report = incompatible_mod_set(log, &get_environment_info(log)).expect("No report");
+        assert!(report
+            .description
+            .contains("Mod 'Sodium' (sodium) 0.5.3 requires"));
+        assert!(report
+            .description
+            .contains("Mod 'Iris' (iris) 1.6.11 requires"));
+        assert!(report
+            .description
+            .contains("**Suggested fix:** Install fabric-api, version 0.92.0 or later."));
+        assert!(dependency_generic(log, &get_environment_info(log)).is_none());
+    }
+
+    #[test]
+    fn incompatible_mod_set_old_loader() {
+        let log = "Mod resolution encountered an incompatible mod set!\nA potential solution has been determined:\n\t - Install fabric-api, version 0.92.0 or later.\nUnmet dependency listing:\n\t - Mod 'Sodium' (sodium) 0.5.3 requires version 0.92.0 or later of fabric-api, which is missing!\n\t - Mod 'Iris' (iris) 1.6.11 requires any version of sodium, which is missing!\n";
+        let report = incompatible_mod_set(log, &get_environment_info(log)).expect("No report");
+        assert!(report
+            .description
+            .contains("- Mod 'Iris' (iris) 1.6.11 requires"));
+        assert!(report
+            .description
+            .contains("**Suggested fix:** Install fabric-api"));
+    }
+
+    #[test]
+    fn fabric_loader_outdated_names_the_installed_version() {
+        let log = "Loading Minecraft 1.20.1 with Fabric Loader 0.14.21\nMod 'Sodium' (sodium) 0.5.8 requires version 0.15.0 or later of fabricloader, which is missing!\n";
+        let report = fabric_loader_outdated(log, &get_environment_info(log)).expect("No report");
+        assert!(report.description.contains("but `0.14.21` is installed"));
+
+        let log = "Loading Minecraft 1.20.1 with Fabric Loader 0.15.11\nMod 'Sodium' (sodium) 0.5.8 requires version 0.15.0 or later of fabricloader, which is missing!\n";
+        assert!(fabric_loader_outdated(log, &get_environment_info(log)).is_none());
+    }
+
+    #[test]
+    fn connection_refused_names_the_server() {
+        let log = "io.netty.channel.AbstractChannel$AnnotatedConnectException: Connection refused: no further information: play.example.com/1.2.3.4:25565\n";
+        let report = connection_refused(log, &get_environment_info(log)).expect("No report");
+        assert!(report
+            .description
+            .contains("at `play.example.com/1.2.3.4:25565`"));
+
+        let log = "io.netty.channel.unix.Errors$NativeIoException: recvAddress(..) failed: Connection reset by peer\n";
+        assert!(connection_refused(log, &get_environment_info(log)).is_none());
+    }
+
+    #[test]
+    fn authentication_errors_are_told_apart() {
+        let log = "Couldn't connect to authentication servers";
+        let ctx = get_environment_info(log);
+        assert!(auth_servers_down(log, &ctx).is_some());
+        assert!(invalid_session(log, &ctx).is_none());
+
+        let log = "Failed to verify username!";
+        let ctx = get_environment_info(log);
+        assert!(invalid_session(log, &ctx).is_some());
+        assert!(auth_servers_down(log, &ctx).is_none());
+    }
+
+    #[test]
+    fn world_corruption_names_the_chunk() {
+        let log = "Couldn't load chunk [12, -4]\n";
+        let report = world_corruption(log, &get_environment_info(log)).expect("No report");
+        assert!(report.description.contains("`12, -4`"));
+
+        let log = "Unknown NBT tag type 99 in world\n";
+        assert!(world_corruption(log, &get_environment_info(log)).is_none());
+    }
+
+    #[test]
+    fn shader_compilation_names_the_program_and_pack() {
+        let log = "Using shaderpack: BSL_v8.2.09.zip\nnet.irisshaders.iris.gl.shader.ShaderCompileException: gbuffers_terrain.fsh: error: undeclared identifier\n";
+        let report = shader_compilation(log, &get_environment_info(log)).expect("No report");
+        assert!(report.description.contains("`gbuffers_terrain.fsh`"));
+        assert!(report.description.contains("`BSL_v8.2.09.zip`"));
+    }
+
+    #[test]
+    fn library_mismatch_links_the_library() {
+        let log = "java.lang.NoSuchMethodError: 'void dev.architectury.event.Event.register(java.lang.Object)'\n";
+        let report = library_mismatch(log, &get_environment_info(log)).expect("No report");
+        assert!(report.description.contains("[Architectury API]"));
+
+        let log = "java.lang.NoClassDefFoundError: me/shedaniel/clothconfig2/api/ConfigBuilder\n";
+        let report = library_mismatch(log, &get_environment_info(log)).expect("No report");
+        assert!(report.description.contains("[Cloth Config]"));
+    }
+
+    #[test]
+    fn api_mismatch_names_the_caller() {
+        let log = "java.lang.NoSuchMethodError: 'void com.example.Foo.bar()'\n\tat net.minecraft.class_1.method_1(class_1.java:1)\n\tat com.example.othermod.Thing.run(Thing.java:5)\n";
+        let report = api_mismatch(log, &get_environment_info(log)).expect("No report");
+        assert!(report
+            .description
+            .contains("method `void com.example.Foo.bar()`"));
+        assert!(report
+            .description
+            .contains("`com.example.othermod` tried to use it"));
+    }
+
+    #[test]
+    fn download_failed_ignores_textures() {
+        let log = "Failed to download https://libraries.minecraft.net/org/lwjgl/lwjgl/3.3.1/lwjgl-3.3.1.jar\n";
+        assert!(download_failed(log, &get_environment_info(log)).is_some());
+
+        let log = "[12:00:00] [Render thread/INFO]: Couldn't download http texture https://textures.minecraft.net/texture/abc\n";
+        assert!(download_failed(log, &get_environment_info(log)).is_none());
+    }
+
+    #[test]
+    fn ssl_handshake_is_not_a_failed_download() {
+        let log = "javax.net.ssl.SSLHandshakeException: PKIX path building failed\n";
+        let ctx = get_environment_info(log);
+        assert!(ssl_handshake(log, &ctx).is_some());
+        assert!(download_failed(log, &ctx).is_none());
+    }
+
+    #[test]
+    fn java_path_shows_the_path() {
+        let log = r#"java.io.IOException: Cannot run program "C:\Program Files\Java\jre1.8.0_51\bin\javaw.exe": CreateProcess error=2"#;
+        let report = java_path(log, &get_environment_info(log)).expect("No report");
+        assert!(report
+            .description
+            .contains(r"`C:\Program Files\Java\jre1.8.0_51\bin\javaw.exe`"));
+    }
+
+    #[test]
+    fn suspected_mods_are_listed_without_urls() {
+        let log = "Suspected Mods:\n\tSodium (sodium)\n\t\tIssue tracker URL: https://github.com/CaffeineMC/sodium/issues\n\tIris (iris)\nStacktrace:\n";
+        let report = suspected_mods(log, &get_environment_info(log)).expect("No report");
+        assert!(report
+            .description
+            .contains("- Sodium (`sodium`)\n- Iris (`iris`)"));
+        assert!(!report.description.contains("Issue tracker"));
+    }
+
+    #[test]
+    fn crash_report_analysis_includes_frames() {
+        let log = "---- Minecraft Crash Report ----\n// Oops.\n\nTime: 2024-01-01 12:00:00\nDescription: Rendering overlay\n\njava.lang.RuntimeException: boom\n\tat a.b.C.d(C.java:1)\n\tat e.f.G.h(G.java:2)\n\n";
+        let report = crash_report_analysis(log, &get_environment_info(log)).expect("No report");
+        assert!(report.description.contains("Context: `Rendering overlay`"));
+        assert!(report
+            .description
+            .contains("\tat a.b.C.d(C.java:1)\n\tat e.f.G.h(G.java:2)"));
+    }
+
+    #[test]
+    fn crash_report_analysis_truncates() {
+        let log = format!("---- Minecraft Crash Report ----\n// Oops.\n\nTime: 2024-01-01 12:00:00\nDescription: Rendering overlay\n\njava.lang.RuntimeException: {}\n", "x".repeat(2000));
+        let report = crash_report_analysis(&log, &get_environment_info(&log)).expect("No report");
+        assert!(report.description.len() <= 1024);
+        assert!(report.description.contains("...\n```"));
+    }
+
+    #[test]
+    fn sodium_version_mismatch_compares_the_build() {
+        let log = "Loading Minecraft 1.20.4 with Fabric Loader 0.15.11\nLoading 1 mods:\n\t- sodium 0.5.3+mc1.20.1\n";
+        assert!(sodium_version_mismatch(log, &get_environment_info(log)).is_some());
+
+        let log = "Loading Minecraft 1.20.1 with Fabric Loader 0.15.11\nLoading 1 mods:\n\t- sodium 0.5.3+mc1.20.1\n";
+        assert!(sodium_version_mismatch(log, &get_environment_info(log)).is_none());
+    }
+
+    #[test]
+    fn memory_exceeds_system_needs_the_ram_size() {
+        let log = "-Xmx16G\nMemory slot #0 capacity (MB): 8192.00\n";
+        let report = memory_exceeds_system(log, &get_environment_info(log)).expect("No report");
+        assert!(report.description.contains("`16384` MiB"));
+
+        let log = "-Xmx16G\n";
+        assert!(memory_exceeds_system(log, &get_environment_info(log)).is_none());
+    }
+
+    #[test]
+    fn gc_flags_only_without_g1() {
+        let log = "Starting minecraft server version 1.20.1\n-Xmx8G\n";
+        assert!(gc_flags(log, &get_environment_info(log)).is_some());
+
+        let log = "Starting minecraft server version 1.20.1\n-Xmx8G\n-XX:+UseG1GC\n";
+        assert!(gc_flags(log, &get_environment_info(log)).is_none());
+    }
+
+    #[test]
+    fn refmap_missing_names_the_config() {
+        let log = "Reference map 'examplemod-refmap.json' for examplemod.mixins.json could not be read. If this is a development environment you can ignore this message\n";
+        let report = refmap_missing(log, &get_environment_info(log)).expect("No report");
+        assert!(report.description.contains("`examplemod.mixins.json`"));
+        assert!(report.description.contains("`examplemod-refmap.json`"));
+    }
+
+    #[test]
+    fn server_overloaded_needs_repeated_warnings() {
+        let log = |count| -> String {
+            (1..=count)
+                .map(|i| format!("[Server thread/WARN]: Can't keep up! Is the server overloaded? Running {}ms or {} ticks behind\n", i * 1000, i * 20))
+                .collect()
+        };
+        let log_5 = log(5);
+        let report = server_overloaded(&log_5, &get_environment_info(&log_5)).expect("No report");
+        assert!(report
+            .description
+            .contains("`5` times and fell up to `5000` ms behind"));
+
+        let log_4 = log(4);
+        assert!(server_overloaded(&log_4, &get_environment_info(&log_4)).is_none());
+    }
+
+    #[test]
+    fn server_watchdog_names_the_stuck_mod() {
+        let log = "A single server tick took 60.00 seconds (should be max 0.05)\nConsidering it to be crashed, server will forcibly shutdown.\n\"Server thread\" daemon prio=5 Id=20 RUNNABLE\n\tat java.lang.Thread.sleep(Native Method)\n\tat com.example.laggymod.TickHandler.onTick(TickHandler.java:42)\n";
+        let report = server_watchdog(log, &get_environment_info(log)).expect("No report");
+        assert!(report.description.contains("`60.00` seconds"));
+        assert!(report
+            .description
+            .contains("stuck in `com.example.laggymod`"));
+    }
+
+    #[test]
+    fn forge_dependency_lists_missing_mods() {
+        let log = "--fml.forgeVersion, 47.2.0\nMod ID: 'geckolib', Requested by: 'alexsmobs', Expected range: '[4.2,)', Actual version: '[MISSING]'\n";
+        let report = forge_dependency(log, &get_environment_info(log)).expect("No report");
+        assert!(report
+            .description
+            .contains("- `alexsmobs` needs `geckolib` in the range `[4.2,)`, but it is missing"));
+
+        let log = "Mod ID: 'geckolib', Requested by: 'alexsmobs', Expected range: '[4.2,)', Actual version: '[MISSING]'\n";
+        assert!(forge_dependency(log, &get_environment_info(log)).is_none());
+    }
+
+    #[test]
+    fn neoforge_incompatible_is_not_a_forge_dependency() {
+        let log = "--fml.neoForgeVersion, 20.4.237\nMod ID: 'neoforge', Requested by: 'create', Expected range: '[20.4.200,)', Actual version: '20.2.86'\n";
+        let ctx = get_environment_info(log);
+        let report = neoforge_incompatible(log, &ctx).expect("No report");
+        assert!(report
+            .description
+            .contains("`create` needs NeoForge `[20.4.200,)`"));
+        assert!(forge_dependency(log, &ctx).is_none());
+    }
+
+    #[test]
+    fn known_mod_crash_names_the_mod() {
+        let log = "\tat gg.essential.handlers.Foo.bar(Foo.java:1)\n";
+        let report = known_mod_crash(log, &get_environment_info(log)).expect("No report");
+        assert_eq!(report.title, "Essential crash");
+
+        let log = "\tat net.digitalingot.feather.Client.start(Client.java:1)\n";
+        let report = known_mod_crash(log, &get_environment_info(log)).expect("No report");
+        assert_eq!(report.title, "Feather crash");
+    }
+
+    #[test]
+    fn mixin_conflict_names_both_mods() {
+        let log = "@Redirect conflict. Skipping sodium.mixins.json:MixinFoo from mod sodium->@Redirect::redirect with priority 1000, already redirected by othermod.mixins.json:MixinBar from mod othermod->@Redirect::redirect2 with priority 1000\n";
+        let report = mixin_conflict(log, &get_environment_info(log)).expect("No report");
+        assert!(report.description.contains("`sodium` and `othermod`"));
+    }
+
+    #[test]
+    fn shaders_without_loader_unless_iris_is_installed() {
+        let log = "Missing metadata in pack file/ComplementaryReimagined_r5.1.zip\n";
+        let report = shaders_without_loader(log, &get_environment_info(log)).expect("No report");
+        assert!(report
+            .description
+            .contains("`ComplementaryReimagined_r5.1.zip`"));
+
+        let log = "Loading 1 mods:\n\t- iris 1.6.11\nMissing metadata in pack file/ComplementaryReimagined_r5.1.zip\n";
+        assert!(shaders_without_loader(log, &get_environment_info(log)).is_none());
+    }
+
+    #[test]
+    fn launcher_log_only_before_the_game_starts() {
+        let log = "0.000 I | Prism Launcher 8.0, (c) 2022-2023 Prism Launcher Contributors\n0.010 D | LaunchController.cpp:120 Launching instance\n";
+        assert!(launcher_log(log, &get_environment_info(log)).is_some());
+
+        let log = format!(
+            "{log}[12:00:00] [main/INFO]: Loading Minecraft 1.20.1 with Fabric Loader 0.15.11\n"
+        );
+        assert!(launcher_log(&log, &get_environment_info(&log)).is_none());
+    }
+
+    #[test]
+    fn fabric_api_mismatch_names_the_caller_and_version() {
+        let log = "Loading 1 mods:\n\t- fabric-api 0.92.2+1.20.1\njava.lang.NoSuchMethodError: 'void net.fabricmc.fabric.api.event.Event.register(java.lang.Object)'\n\tat com.example.oldmod.Init.onInitialize(Init.java:10)\n";
+        let ctx = get_environment_info(log);
+        let report = fabric_api_mismatch(log, &ctx).expect("No report");
+        assert!(report
+            .description
+            .contains("net.fabricmc.fabric.api.event.Event.register"));
+        assert!(report
+            .description
+            .contains("called by `com.example.oldmod`"));
+        assert!(report.description.contains("version is `0.92.2+1.20.1`"));
+        assert!(api_mismatch(log, &ctx).is_none());
+    }
+
+    #[test]
+    fn resource_and_data_packs_are_told_apart() {
+        let log = "Exception loading blockstate definition: 'minecraft:blockstates/stone.json' in resourcepack: 'file/BrokenPack.zip'\n";
+        let ctx = get_environment_info(log);
+        let report = resource_pack_error(log, &ctx).expect("No report");
+        assert!(report.description.contains(
+            "The file `minecraft:blockstates/stone.json` in `file/BrokenPack.zip` is broken."
+        ));
+        assert!(datapack_error(log, &ctx).is_none());
+
+        let log =
+            "Couldn't parse data file minecraft:recipes/foo from file/mypack.zip: Unknown item\n";
+        let ctx = get_environment_info(log);
+        let report = datapack_error(log, &ctx).expect("No report");
+        assert!(report.description.contains("in `file/mypack.zip`"));
+        assert!(resource_pack_error(log, &ctx).is_none());
+    }
+}
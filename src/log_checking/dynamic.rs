@@ -0,0 +1,197 @@
+use std::{collections::HashSet, fs, io, path::Path};
+
+use anyhow::{bail, Context, Result};
+use regex::{Captures, Regex};
+use serde::Deserialize;
+
+use super::checks::{all_checks, CheckReport, Severity};
+
+pub const DYNAMIC_CHECKS_PATH: &str = "checks.toml";
+
+#[derive(Deserialize)]
+struct DynamicChecksFile {
+    #[serde(default, rename = "check")]
+    checks: Vec<DynamicCheckDefinition>,
+}
+
+#[derive(Deserialize)]
+struct DynamicCheckDefinition {
+    id: String,
+    pattern: String,
+    title: String,
+    description: String,
+    severity: Severity,
+}
+
+/// A check defined by a pattern in `checks.toml`, so new patterns don't need a code change
+#[derive(Debug)]
+pub struct DynamicCheck {
+    pub id: &'static str,
+    pub title: String,
+    /// Can contain `{1}`, `{2}`, ... for the capture groups of the pattern
+    pub description: String,
+    pub severity: Severity,
+    regex: Regex,
+}
+
+impl DynamicCheck {
+    pub fn run(&self, log: &str) -> Option<CheckReport> {
+        let captures = self.regex.captures(log)?;
+        Some(CheckReport {
+            title: self.title.clone(),
+            description: interpolate(&self.description, &captures),
+            severity: self.severity,
+        })
+    }
+}
+
+const GROUP_REFERENCE: &str = r"\{(\d+)\}";
+
+fn interpolate(template: &str, captures: &Captures) -> String {
+    crate::cached_regex!(GROUP_REFERENCE)
+        .replace_all(template, |group: &Captures| {
+            group[1]
+                .parse()
+                .ok()
+                .and_then(|i| captures.get(i))
+                .map_or("", |capture| capture.as_str())
+                .to_string()
+        })
+        .into_owned()
+}
+
+/// The capture groups referenced by a template, numbers too large to parse are never valid
+fn referenced_groups(template: &str) -> impl Iterator<Item = usize> + '_ {
+    crate::cached_regex!(GROUP_REFERENCE)
+        .captures_iter(template)
+        .map(|group| group[1].parse().unwrap_or(usize::MAX))
+}
+
+/// Not having any dynamic checks is fine, so a missing file is too
+pub fn load_dynamic_checks(path: impl AsRef<Path>) -> Result<Vec<DynamicCheck>> {
+    let toml = match fs::read_to_string(path) {
+        Ok(toml) => toml,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(err) => return Err(err.into()),
+    };
+    parse_dynamic_checks(&toml)
+}
+
+fn parse_dynamic_checks(toml: &str) -> Result<Vec<DynamicCheck>> {
+    let file: DynamicChecksFile = toml::from_str(toml)?;
+
+    let mut ids = HashSet::new();
+    file.checks
+        .into_iter()
+        .map(|check| {
+            // Reports, stats and false positives are all keyed by the id
+            if all_checks().any(|built_in| built_in.id == check.id) {
+                bail!("Check `{}` has the same id as a built-in check", check.id);
+            }
+            if !ids.insert(check.id.clone()) {
+                bail!("There is more than one check with the id `{}`", check.id);
+            }
+            let regex = Regex::new(&check.pattern)
+                .with_context(|| format!("Invalid pattern for check `{}`", check.id))?;
+            // Group 0 is the whole match
+            let groups = regex.captures_len() - 1;
+            if let Some(group) = referenced_groups(&check.description).find(|group| *group > groups)
+            {
+                bail!(
+                    "The description of check `{}` references group {{{group}}}, but its pattern only has {groups} groups",
+                    check.id
+                );
+            }
+            Ok(DynamicCheck {
+                // Loaded once at startup, so ids can be used like the compiled checks' ids
+                id: check.id.leak(),
+                title: check.title,
+                description: check.description,
+                severity: check.severity,
+                regex,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loads_the_sample_file() {
+        let checks =
+            load_dynamic_checks(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/checks.toml")).unwrap();
+        assert_eq!(checks.len(), 1);
+        assert_eq!(checks[0].id, "sinytra_connector");
+        assert_eq!(checks[0].severity, Severity::Medium);
+
+        let report = checks[0]
+            .run("Mod 'create' requires Sinytra Connector 1.0.0")
+            .expect("No report");
+        assert_eq!(report.title, "Sinytra Connector required");
+        assert_eq!(
+            report.description,
+            "`create` needs Sinytra Connector `1.0.0`."
+        );
+        assert!(checks[0].run("Loading Minecraft 1.20.1").is_none());
+    }
+
+    #[test]
+    fn missing_file_has_no_checks() {
+        assert!(load_dynamic_checks("does-not-exist.toml")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn ids_must_be_unique() {
+        let check = |id| {
+            format!("[[check]]\nid = \"{id}\"\npattern = \"boom\"\ntitle = \"Boom\"\ndescription = \"It went boom.\"\nseverity = \"High\"\n")
+        };
+        let err = parse_dynamic_checks(&check("out_of_memory")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Check `out_of_memory` has the same id as a built-in check"
+        );
+        let err = parse_dynamic_checks(&format!("{}{}", check("boom"), check("boom"))).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "There is more than one check with the id `boom`"
+        );
+    }
+
+    #[test]
+    fn out_of_range_group_is_an_error() {
+        let toml = r#"
+[[check]]
+id = "sinytra_connector"
+pattern = "Mod '(\\S+)' requires Sinytra Connector ([\\d.]+)"
+title = "Sinytra Connector required"
+description = "`{1}` needs Sinytra Connector `{3}`."
+severity = "Medium"
+"#;
+        let err = parse_dynamic_checks(toml).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The description of check `sinytra_connector` references group {3}, but its pattern only has 2 groups"
+        );
+    }
+
+    #[test]
+    fn invalid_pattern_is_an_error() {
+        let toml = r#"
+[[check]]
+id = "sinytra_connector"
+pattern = "Mod '(\\S+)' requires Sinytra Connector ([\\d.]+"
+title = "Sinytra Connector required"
+description = "`{1}` needs Sinytra Connector `{2}`."
+severity = "Medium"
+"#;
+        let err = parse_dynamic_checks(toml).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid pattern for check `sinytra_connector`"
+        );
+    }
+}
@@ -1,7 +1,5 @@
 use std::fmt::Display;
 
-use regex::Regex;
-
 pub enum ModLoader {
     Fabric(Option<String>),
     Forge,
@@ -33,16 +31,82 @@ pub enum Launcher {
     MultiMC,
     Vanilla,
     CurseForge,
+    ModrinthApp,
+    ATLauncher,
+    GDLauncher,
+}
+
+impl Launcher {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Prism => "Prism Launcher",
+            Self::PolyMC => "PolyMC",
+            Self::MultiMC => "MultiMC",
+            Self::Vanilla => "Minecraft Launcher",
+            Self::CurseForge => "CurseForge",
+            Self::ModrinthApp => "Modrinth App",
+            Self::ATLauncher => "ATLauncher",
+            Self::GDLauncher => "GDLauncher",
+        }
+    }
 }
 
+/// Only some launchers have an emoji on the bot's server, so all of them are shown by name
 impl Display for Launcher {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+pub struct OsInfo {
+    pub name: String,
+    pub arch: Option<String>,
+}
+
+impl OsInfo {
+    pub fn is_windows(&self) -> bool {
+        self.name.starts_with("Windows")
+    }
+
+    pub fn is_mac(&self) -> bool {
+        self.name.starts_with("Mac") || self.name.starts_with("macOS")
+    }
+
+    pub fn is_linux(&self) -> bool {
+        self.name.starts_with("Linux")
+    }
+}
+
+impl Display for OsInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)?;
+        if let Some(arch) = &self.arch {
+            write!(f, " (`{}`)", arch)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub enum Side {
+    Client,
+    Server,
+}
+
+impl Side {
+    pub fn other(self) -> Self {
         match self {
-            Self::Prism => write!(f, "<:prism:1246451647677468714>"),
-            Self::PolyMC => write!(f, "<:polymc:1246451649212448860>"),
-            Self::MultiMC => write!(f, "<:multimc:1246451644342865992>"),
-            Self::Vanilla => write!(f, "<:minecraft:1246451645441642496>"),
-            Self::CurseForge => write!(f, "<:curseforge:1246451646909911141>"),
+            Self::Client => Self::Server,
+            Self::Server => Self::Client,
+        }
+    }
+}
+
+impl Display for Side {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Client => write!(f, "client"),
+            Self::Server => write!(f, "server"),
         }
     }
 }
@@ -55,7 +119,143 @@ pub struct EnvironmentContext {
     pub launcher: Option<Launcher>,
     pub mc_version: Option<String>,
     pub loader: Option<ModLoader>,
+    pub java_version: Option<u32>,
+    pub java_vendor: Option<String>,
+    pub os: Option<OsInfo>,
+    pub side: Option<Side>,
+    /// The maximum heap size in MiB
+    pub max_memory_mb: Option<u64>,
+    /// The physical memory of the system in MiB
+    pub system_memory_mb: Option<u64>,
     pub known_mods: Vec<DiscoveredMod>,
+    /// Every top-level mod from the loader's mod list as `(id, version)`
+    pub mods: Vec<(String, String)>,
+}
+
+impl EnvironmentContext {
+    /// The minor and patch components of a release version, e.g. `(20, 1)` for `1.20.1`
+    pub fn mc_version_numbers(&self) -> Option<(u32, u32)> {
+        let parts = parse_version(self.mc_version.as_ref()?)?;
+        match parts[..] {
+            [1, minor] => Some((minor, 0)),
+            [1, minor, patch, ..] => Some((minor, patch)),
+            _ => None,
+        }
+    }
+
+    pub fn required_java_version(&self) -> Option<u32> {
+        Some(match self.mc_version_numbers()? {
+            (minor, patch) if minor > 20 || (minor == 20 && patch >= 5) => 21,
+            (minor, _) if minor >= 18 => 17,
+            (17, _) => 16,
+            _ => 8,
+        })
+    }
+
+    /// The id of the mod owning a package or class, from its known root package or otherwise
+    /// an installed mod's id appearing in the package, e.g. `create` for `com.simibubi.create`
+    pub fn attribute_package(&self, package: &str) -> Option<String> {
+        if let Some((_, id)) = MOD_PACKAGES
+            .iter()
+            .find(|(root, _)| package.starts_with(root))
+        {
+            return Some(id.to_string());
+        }
+
+        let segments: Vec<_> = package.split('.').collect();
+        self.mods
+            .iter()
+            .map(|(id, _)| id.as_str())
+            .chain(self.known_mods.iter().map(|known| known.0 .0))
+            // Packages are often named after what they target
+            .filter(|id| !matches!(*id, "minecraft" | "java" | "fabricloader"))
+            .find(|id| {
+                let id = id.replace('-', "_");
+                segments
+                    .iter()
+                    .any(|segment| segment.eq_ignore_ascii_case(&id))
+            })
+            .map(ToString::to_string)
+    }
+}
+
+/// Root packages of popular mods that aren't named after their id
+const MOD_PACKAGES: &[(&str, &str)] = &[
+    ("me.jellysquid.mods.sodium.", "sodium"),
+    ("net.caffeinemc.mods.sodium.", "sodium"),
+    ("me.jellysquid.mods.lithium.", "lithium"),
+    ("net.caffeinemc.mods.lithium.", "lithium"),
+    ("net.coderbot.iris.", "iris"),
+    ("net.irisshaders.iris.", "iris"),
+    ("net.fabricmc.fabric.", "fabric-api"),
+    ("dev.architectury.", "architectury"),
+    ("me.shedaniel.clothconfig2.", "cloth-config"),
+    ("mezz.jei.", "jei"),
+    ("snownee.jade.", "jade"),
+];
+
+/// The heap limit the JVM reports in crash reports, otherwise the last `-Xmx` argument since that's
+/// the one the JVM uses, or else `-XX:MaxRAMPercentage` of the system memory
+fn parse_max_memory(log: &str, system_memory_mb: Option<u64>) -> Option<u64> {
+    if let Some(Some(mib)) = grab!(log, r"up to \d+ bytes \((\d+) MiB\)") {
+        return mib.parse().ok();
+    }
+
+    if let Some(captures) = crate::cached_regex!(r"-Xmx(\d+)([kKmMgG]?)\b")
+        .captures_iter(log)
+        .last()
+    {
+        let amount: u64 = captures.get(1).expect("Regex err").as_str().parse().ok()?;
+        return Some(match captures.get(2).map(|unit| unit.as_str()) {
+            Some("g" | "G") => amount * 1024,
+            Some("m" | "M") => amount,
+            Some("k" | "K") => amount / 1024,
+            _ => amount / 1024 / 1024,
+        });
+    }
+
+    let percentage: f64 = crate::cached_regex!(r"-XX:MaxRAMPercentage=(\d+(?:\.\d+)?)")
+        .captures_iter(log)
+        .last()?
+        .get(1)
+        .expect("Regex err")
+        .as_str()
+        .parse()
+        .ok()?;
+    Some((system_memory_mb? as f64 * percentage / 100.0) as u64)
+}
+
+/// Crash reports list every memory stick, some loaders print the total instead
+fn parse_system_memory(log: &str) -> Option<u64> {
+    let slots: u64 = crate::cached_regex!(r"Memory slot #\d+ capacity \(MB\): (\d+)")
+        .captures_iter(log)
+        .filter_map(|captures| captures.get(1)?.as_str().parse::<u64>().ok())
+        .sum();
+    if slots > 0 {
+        return Some(slots);
+    }
+
+    grab!(log, r"(?i)total physical memory(?: size)?:? (\d+) ?MB")
+        .flatten()
+        .and_then(|memory| memory.parse().ok())
+}
+
+/// The feature release of a Java version string, e.g. `17` for `17.0.8` and `8` for `1.8.0_392`
+pub fn parse_java_version(version: &str) -> Option<u32> {
+    let mut parts = version.split(['.', '_', '+', '-']);
+    match parts.next()?.parse().ok()? {
+        1 => parts.next()?.parse().ok(),
+        major => Some(major),
+    }
+}
+
+pub fn parse_version(version: &str) -> Option<Vec<u32>> {
+    let release = version.split(['+', '-']).next()?;
+    release
+        .split('.')
+        .map(|part| part.parse().ok())
+        .collect::<Option<Vec<u32>>>()
+        .filter(|parts| !parts.is_empty())
 }
 
 impl Display for EnvironmentContext {
@@ -69,22 +269,47 @@ impl Display for EnvironmentContext {
         if let Some(loader) = &self.loader {
             write!(f, "**Loader:** {}\n", loader)?;
         }
+        if let Some(side) = &self.side {
+            write!(f, "**Side:** {}\n", side)?;
+        }
+        if let Some(os) = &self.os {
+            write!(f, "**OS:** {}\n", os)?;
+        }
+        if let Some(version) = &self.java_version {
+            write!(f, "**Java:** `{}`", version)?;
+            if let Some(vendor) = &self.java_vendor {
+                write!(f, " ({})", vendor)?;
+            }
+            write!(f, "\n")?;
+        }
+        if let Some(memory) = &self.max_memory_mb {
+            write!(f, "**Memory:** `{}` MiB\n", memory)?;
+        }
         if !self.known_mods.is_empty() {
             write!(f, "\n")?;
             write!(f, "**Known Mods:**\n")?;
             for ele in &self.known_mods {
-                write!(f, "- {} `{}`\n", ele.0.1, ele.1)?;
+                write!(f, "- {} `{}`\n", ele.0 .1, ele.1)?;
             }
         }
         Ok(())
     }
 }
 
+/// Compiles the pattern once per call site, so it must not change between invocations
+#[macro_export]
+macro_rules! cached_regex {
+    ($arg:expr) => {{
+        static REGEX: ::std::sync::OnceLock<::regex::Regex> = ::std::sync::OnceLock::new();
+        REGEX.get_or_init(|| ::regex::Regex::new($arg).expect("Incorrect regex"))
+    }};
+}
+
 #[macro_export]
 macro_rules! grab_all {
     ($log:expr,$($arg:expr),*) => {'a: {
         $(
-            if let Some(cap) = Regex::new($arg).expect("Incorrect regex").captures($log) {
+            if let Some(cap) = $crate::cached_regex!($arg).captures($log) {
                 break 'a Some(cap);
             }
         )*
@@ -96,7 +321,7 @@ macro_rules! grab_all {
 macro_rules! grab {
     ($log:expr,$($arg:expr),*) => {'a: {
         $(
-            if let Some(cap) = Regex::new($arg).expect("Incorrect regex").captures($log) {
+            if let Some(cap) = $crate::cached_regex!($arg).captures($log) {
                 break 'a Some(cap.get(1).map(|m| m.as_str().to_string()));
             }
         )*
@@ -121,74 +346,111 @@ macro_rules! known_mods {
     }};
 }
 
+/// Parses the `Loading N mods:` list printed by Fabric Loader, bundled sub-mods are skipped
+fn parse_mod_list(log: &str) -> Vec<(String, String)> {
+    let Some(start) = crate::cached_regex!(r"Loading \d+ mods:\r?\n").find(log) else {
+        return vec![];
+    };
+
+    let mut mods = vec![];
+    for line in log[start.end()..].lines() {
+        let line = line.trim_start();
+        if let Some(entry) = line.strip_prefix("- ") {
+            let mut parts = entry.split_whitespace();
+            if let (Some(id), Some(version)) = (parts.next(), parts.next()) {
+                mods.push((id.to_string(), version.to_string()));
+            }
+        } else if !(line.starts_with("|--") || line.starts_with("\\--")) {
+            break;
+        }
+    }
+    mods
+}
+
 pub fn get_environment_info(log: &str) -> EnvironmentContext {
-    let launcher = if let Some(_) = grab!(
+    let launcher = if let Some(_) = grab!(log, r"Prism Launcher version:") {
+        Some(Launcher::Prism)
+    } else if let Some(_) = grab!(log, r"PolyMC version:") {
+        Some(Launcher::PolyMC)
+    } else if let Some(_) = grab!(log, r"MultiMC version:") {
+        Some(Launcher::MultiMC)
+    } else if let Some(_) = grab!(
         log,
-        r"Prism Launcher version:"
+        r"[/\\]com\.modrinth\.theseus[/\\]",
+        r"[/\\]ModrinthApp[/\\]profiles[/\\]"
     ) {
-        Some(Launcher::Prism)
+        Some(Launcher::ModrinthApp)
     } else if let Some(_) = grab!(
         log,
-        r"PolyMC version:"
+        r"(?i)[/\\]curseforge[/\\]minecraft[/\\](?:Instances|Install)[/\\]"
     ) {
-        Some(Launcher::PolyMC)
+        Some(Launcher::CurseForge)
     } else if let Some(_) = grab!(
         log,
-        r"MultiMC version:"
+        r"ATLauncher Version:",
+        r"[/\\]ATLauncher[/\\]instances[/\\]"
     ) {
-        Some(Launcher::MultiMC)
+        Some(Launcher::ATLauncher)
+    } else if let Some(_) = grab!(
+        log,
+        r"(?i)[/\\]gdlauncher(?:_next|_carbon)?[/\\](?:instances|data[/\\]instances)[/\\]"
+    ) {
+        Some(Launcher::GDLauncher)
     } else {
         None
     };
 
-
     let mut loader = None;
 
-    if let Some(fabric_version) = grab!(
+    if let Some(quilt_version) = grab!(
+        log,
+        r"Loading Minecraft [^\s]+ with Quilt Loader ([^\s]+)",
+        r"Quilt Loader: Quilt Loader ([^\s]+)",
+        r"quilt_loader: Quilt Loader ([^\s]+)",
+        r"org\.quiltmc\.loader",
+        r"Is Modded: Definitely; [^\s]+ brand changed to 'quilt'"
+    ) {
+        loader = Some(ModLoader::Quilt(quilt_version));
+    } else if let Some(fabric_version) = grab!(
         log,
         r"Loading Minecraft [^\s]+ with Fabric Loader ([^\s]+)",
         r"fabricloader: Fabric Loader ([^\s]+)",
         r"Is Modded: Definitely; [^\s]+ brand changed to 'fabric'"
     ) {
         loader = Some(ModLoader::Fabric(fabric_version));
-    } else if let Some(_) = grab!(
-        log,
-        r"ne\.mi\.fm\.lo",
-        r"Is Modded: Definitely; [^\s]+ brand changed to 'forge'"
-    ) {
-        loader = Some(ModLoader::Forge);
     } else if let Some(_) = grab!(
         log,
         r"net\.neoforged\.fml\.loading",
+        r"--fml\.neoForgeVersion",
+        r"--launchTarget, neoforge(?:client|server)",
         r"Is Modded: Definitely; [^\s]+ brand changed to 'neoforge'"
     ) {
         loader = Some(ModLoader::NeoForge);
-    } else if let Some(quilt_version) = grab!(
+    } else if let Some(_) = grab!(
         log,
-        r"Loading Minecraft [^\s]+ with Quilt Loader ([^\s]+)",
-        r"Is Modded: Definitely; [^\s]+ brand changed to 'quilt'"
+        r"ne\.mi\.fm\.lo",
+        r"--fml\.forgeVersion",
+        r"--launchTarget, forge(?:client|server)",
+        r"MinecraftForge v\S+ Initialized",
+        r"Is Modded: Definitely; [^\s]+ brand changed to 'forge'"
     ) {
-        loader = Some(ModLoader::Quilt(quilt_version));
+        loader = Some(ModLoader::Forge);
     }
 
     let mc_version = grab!(
         log,
         r"Loading Minecraft ([^\s]+)",
         r"minecraft server version ([^\s]+)",
-        r"Minecraft Version: ([^\s]+)"
+        r"Minecraft Version: ([^\s]+)",
+        r"--fml\.mcVersion, ([^\s,]+)",
+        r"for MC ([^\s]+) with MCP"
     )
     .map(|o| o.expect("Regex error!!!"));
 
     let known_mods = known_mods!(
         log,
-        ScanMod(
-            "fabric",
-            "<:fabric:1246103308842700831> Fabric API"
-        ),
-        ScanMod(
-            "fabric-api",
-            "<:fabric:1246103308842700831> Fabric API"
-        ),
+        ScanMod("fabric", "<:fabric:1246103308842700831> Fabric API"),
+        ScanMod("fabric-api", "<:fabric:1246103308842700831> Fabric API"),
         ScanMod(
             "do_a_barrel_roll",
             "<:doabarrelroll:1107712867823792210> Do a Barrel Roll"
@@ -237,10 +499,7 @@ pub fn get_environment_info(log: &str) -> EnvironmentContext {
             "restart_detector",
             "<:restartdetector:1172685600000847922> Restart Detector"
         ),
-        ScanMod(
-            "cicada",
-            "<:cicada:1246197518807863367> CICADA"
-        ),
+        ScanMod("cicada", "<:cicada:1246197518807863367> CICADA"),
         ScanMod(
             "elytratrims",
             "<:elytratrims:1246408624423702558> Elytra Trims"
@@ -249,25 +508,282 @@ pub fn get_environment_info(log: &str) -> EnvironmentContext {
             "soundboard",
             "<:soundboard:1246447385362698280> Voice Chat Soundboard"
         ),
-        ScanMod(
-            "owo",
-            "<:owo:1246492160027656273> oωo (owo-lib)"
-        ),
+        ScanMod("owo", "<:owo:1246492160027656273> oωo (owo-lib)"),
+        ScanMod("sodium", "Sodium"),
+        ScanMod("iris", "Iris Shaders"),
         // Shitass mods lmao
-        ScanMod(
-            "optifabric",
-            "<:optifabric:1246484303110606978> OptiFabric"
-        ),
-        ScanMod(
-            "bclib",
-            "<:bclib:1246585932379852901> BCLib"
-        )
+        ScanMod("optifabric", "<:optifabric:1246484303110606978> OptiFabric"),
+        ScanMod("bclib", "<:bclib:1246585932379852901> BCLib")
     );
 
+    let java_version = grab!(
+        log,
+        r"Java is version ([^\s,]+),",
+        r"Java Version: ([^\s,]+)",
+        r"java version \x22?([^\s\x22]+)",
+        r"openjdk version \x22?([^\s\x22]+)"
+    )
+    .flatten()
+    .and_then(|version| parse_java_version(&version));
+
+    let java_vendor = grab!(
+        log,
+        r"Java is version [^\s,]+, using \S+ \(\S+\) architecture, from ([^\r\n]+?)\.?(?:\r?\n|$)",
+        r"Java Version: [^\s,]+, ([^\r\n]+)"
+    )
+    .flatten()
+    .map(|vendor| vendor.trim().to_string());
+
+    let os = grab_all!(
+        log,
+        r"Operating System: ([^\r\n(]+?) \((\w+)\)",
+        r"Operating System: ([^\r\n]+)"
+    )
+    .map(|captures| OsInfo {
+        name: captures
+            .get(1)
+            .expect("Regex err")
+            .as_str()
+            .trim()
+            .to_string(),
+        arch: captures.get(2).map(|arch| arch.as_str().to_string()),
+    });
+
+    let side = if grab!(
+        log,
+        r"net\.minecraft\.server\.Main",
+        r"net\.fabricmc\.loader\.impl\.launch\.knot\.KnotServer",
+        r"org\.quiltmc\.loader\.impl\.launch\.knot\.KnotServer",
+        r"--launchTarget, (?:forge|neoforge)server",
+        r"\bDedicatedServer\b",
+        r"Starting minecraft server version"
+    )
+    .is_some()
+    {
+        Some(Side::Server)
+    } else if grab!(
+        log,
+        r"net\.minecraft\.client\.main\.Main",
+        r"net\.fabricmc\.loader\.impl\.launch\.knot\.KnotClient",
+        r"org\.quiltmc\.loader\.impl\.launch\.knot\.KnotClient",
+        r"--launchTarget, (?:forge|neoforge)client",
+        r"Setting user: "
+    )
+    .is_some()
+    {
+        Some(Side::Client)
+    } else {
+        None
+    };
+
+    // Needed to resolve -XX:MaxRAMPercentage
+    let system_memory_mb = parse_system_memory(log);
+
     EnvironmentContext {
         launcher,
         mc_version,
         loader,
+        java_version,
+        java_vendor,
+        os,
+        side,
+        max_memory_mb: parse_max_memory(log, system_memory_mb),
+        system_memory_mb,
         known_mods,
+        mods: parse_mod_list(log),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_version_ignores_build_metadata() {
+        assert_eq!(parse_version("0.92.2+1.20.1"), Some(vec![0, 92, 2]));
+        assert_eq!(parse_version("1.20.1"), Some(vec![1, 20, 1]));
+        assert_eq!(parse_version("0.5.8-beta.1"), Some(vec![0, 5, 8]));
+        assert_eq!(parse_version("24w14a"), None);
+    }
+
+    #[test]
+    fn launchers_are_shown_by_name() {
+        assert_eq!(Launcher::Prism.to_string(), "Prism Launcher");
+        assert_eq!(Launcher::ModrinthApp.to_string(), "Modrinth App");
+    }
+
+    #[test]
+    fn cached_regex_compiles_once() {
+        fn pattern() -> &'static regex::Regex {
+            crate::cached_regex!(r"Loading Minecraft (\S+)")
+        }
+        assert!(std::ptr::eq(pattern(), pattern()));
+        assert_eq!(
+            grab!("Loading Minecraft 1.20.1", r"Loading Minecraft (\S+)"),
+            Some(Some("1.20.1".to_string()))
+        );
+    }
+
+    #[test]
+    fn mc_version_numbers() {
+        let numbers = |log| get_environment_info(log).mc_version_numbers();
+        assert_eq!(
+            numbers("Loading Minecraft 1.20.1 with Fabric Loader 0.15.11"),
+            Some((20, 1))
+        );
+        assert_eq!(
+            numbers("Loading Minecraft 1.21 with Fabric Loader 0.15.11"),
+            Some((21, 0))
+        );
+        assert_eq!(
+            numbers("Loading Minecraft 24w14a with Fabric Loader 0.15.11"),
+            None
+        );
+    }
+
+    #[test]
+    fn java_version_and_vendor() {
+        let temurin = get_environment_info(
+            "Java is version 17.0.8, using 64-bit (amd64) architecture, from Eclipse Adoptium.\n",
+        );
+        assert_eq!(temurin.java_version, Some(17));
+        assert_eq!(temurin.java_vendor.as_deref(), Some("Eclipse Adoptium"));
+
+        let zulu = get_environment_info("Java is version 21.0.2, using 64-bit (aarch64) architecture, from Azul Systems, Inc.\n");
+        assert_eq!(zulu.java_version, Some(21));
+        assert_eq!(zulu.java_vendor.as_deref(), Some("Azul Systems, Inc"));
+
+        let oracle = get_environment_info("Java Version: 1.8.0_392, Oracle Corporation\n");
+        assert_eq!(oracle.java_version, Some(8));
+        assert_eq!(oracle.java_vendor.as_deref(), Some("Oracle Corporation"));
+    }
+
+    #[test]
+    fn forge_and_neoforge_are_told_apart() {
+        let loader = |log| get_environment_info(log).loader;
+        assert!(matches!(
+            loader("--fml.neoForgeVersion, 20.4.237, --fml.mcVersion, 1.20.4"),
+            Some(ModLoader::NeoForge)
+        ));
+        assert!(matches!(
+            loader("--fml.forgeVersion, 47.2.0, --fml.mcVersion, 1.20.1"),
+            Some(ModLoader::Forge)
+        ));
+    }
+
+    #[test]
+    fn quilt_is_not_fabric() {
+        let loader = |log| get_environment_info(log).loader;
+        assert!(matches!(
+            loader("Loading Minecraft 1.20.1 with Quilt Loader 0.26.0"),
+            Some(ModLoader::Quilt(Some(version))) if version == "0.26.0"
+        ));
+        assert!(matches!(
+            loader("Loading Minecraft 1.20.1 with Fabric Loader 0.15.11"),
+            Some(ModLoader::Fabric(Some(version))) if version == "0.15.11"
+        ));
+    }
+
+    #[test]
+    fn mod_list_skips_nested_mods() {
+        let mods = parse_mod_list(
+            "Loading 3 mods:\n\t- fabric-api 0.92.2+1.20.1\n\t   |-- fabric-api-base 0.4.31+1802ada577\n\t   \\-- fabric-networking-api-v1 1.3.11+503a202477\n\t- java 17\n\t- sodium 0.5.8+mc1.20.1\n[main/INFO]: SpongePowered MIXIN Subsystem Version=0.8.5\n",
+        );
+        assert_eq!(
+            mods,
+            [
+                ("fabric-api".to_string(), "0.92.2+1.20.1".to_string()),
+                ("java".to_string(), "17".to_string()),
+                ("sodium".to_string(), "0.5.8+mc1.20.1".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn os_info() {
+        let os = |log| get_environment_info(log).os.expect("No OS");
+
+        let windows = os("Operating System: Windows 10 (amd64) version 10.0\n");
+        assert!(windows.is_windows());
+        assert_eq!(windows.arch.as_deref(), Some("amd64"));
+
+        assert!(os("Operating System: Linux (amd64) version 6.5.0\n").is_linux());
+
+        let mac = os("Operating System: Mac OS X (aarch64) version 14.1\n");
+        assert!(mac.is_mac());
+        assert_eq!(mac.arch.as_deref(), Some("aarch64"));
+    }
+
+    #[test]
+    fn side() {
+        let side = |log| get_environment_info(log).side;
+        assert!(side("Launched main class net.minecraft.client.main.Main") == Some(Side::Client));
+        assert!(side("Starting minecraft server version 1.20.1") == Some(Side::Server));
+        assert!(side("Loading Minecraft 1.20.1 with Fabric Loader 0.15.11").is_none());
+    }
+
+    #[test]
+    fn modrinth_app_and_curseforge() {
+        let launcher = |log| get_environment_info(log).launcher;
+        assert!(matches!(
+            launcher(r"C:\Users\me\AppData\Roaming\ModrinthApp\profiles\Fabulously Optimized\mods"),
+            Some(Launcher::ModrinthApp)
+        ));
+        assert!(matches!(
+            launcher(r"C:\Users\me\curseforge\minecraft\Instances\All the Mods 9\mods"),
+            Some(Launcher::CurseForge)
+        ));
+    }
+
+    #[test]
+    fn atlauncher_and_gdlauncher() {
+        let launcher = |log| get_environment_info(log).launcher;
+        assert!(matches!(
+            launcher("/home/me/ATLauncher/instances/Pack/mods"),
+            Some(Launcher::ATLauncher)
+        ));
+        assert!(matches!(
+            launcher("/home/me/.config/gdlauncher_next/instances/Pack/mods"),
+            Some(Launcher::GDLauncher)
+        ));
+    }
+
+    #[test]
+    fn max_memory() {
+        assert_eq!(parse_max_memory("-Xms512M -Xmx2048M", None), Some(2048));
+        assert_eq!(parse_max_memory("-Xmx4G -Xmx8G", None), Some(8 * 1024));
+        assert_eq!(
+            parse_max_memory("-XX:MaxRAMPercentage=50.0", Some(8192)),
+            Some(4096)
+        );
+        assert_eq!(parse_max_memory("-XX:MaxRAMPercentage=50.0", None), None);
+        // -Xmx takes precedence
+        assert_eq!(
+            parse_max_memory("-XX:MaxRAMPercentage=50 -Xmx6G", Some(8192)),
+            Some(6 * 1024)
+        );
+    }
+
+    #[test]
+    fn max_ram_percentage_uses_system_memory() {
+        let ctx = get_environment_info(
+            "Memory slot #0 capacity (MB): 4096.00\nMemory slot #1 capacity (MB): 4096.00\nJVM Flags: 1 total; -XX:MaxRAMPercentage=75\n",
+        );
+        assert_eq!(ctx.system_memory_mb, Some(8192));
+        assert_eq!(ctx.max_memory_mb, Some(6144));
+    }
+
+    #[test]
+    fn attribute_package() {
+        let ctx = get_environment_info("Loading 1 mods:\n\t- create 0.5.1\n");
+        assert_eq!(
+            ctx.attribute_package("me.jellysquid.mods.sodium.client.render"),
+            Some("sodium".to_string())
+        );
+        assert_eq!(
+            ctx.attribute_package("com.simibubi.create.content.trains"),
+            Some("create".to_string())
+        );
+        assert_eq!(ctx.attribute_package("com.example.unknown"), None);
     }
 }
@@ -1,43 +1,30 @@
-use crate::log_upload;
-
 use self::{
-    checks::{check_checks, Severity},
+    checks::{check_checks, CheckReport, CheckSettings},
     environment::get_environment_info,
 };
-use serenity::all::CreateEmbed;
-use tokio::time::Instant;
 
 pub mod checks;
+pub mod dynamic;
 pub mod environment;
 
-pub fn check_logs(log: &str, name: &str, t: &log_upload::LogType) -> CreateEmbed {
-    let start = Instant::now();
+/// Runs every check on a log, without anything Discord specific
+pub fn analyze_log(log: &str) -> Vec<CheckReport> {
     let ctx = get_environment_info(log);
-    let checks = check_checks(log, &ctx);
-    let severity = checks
-        .iter()
-        .map(|r| r.severity)
-        .max()
-        .unwrap_or(Severity::None);
-    let took = Instant::now() - start;
-
-    let mut embed = CreateEmbed::new()
-        .title(t.title_format(name, &took))
-        .color(severity.get_color())
-        .description(format!(
-            "{ctx}{}",
-            if checks.is_empty() {
-                ""
-            } else if matches!(severity, Severity::None) {
-                "\n**More Information:**\n"
-            } else {
-                "\n**Potential Issues Detected:**\n"
-            }
-        ));
-
-    for ele in &checks {
-        embed = embed.field(format!("- {}", &ele.title), &ele.description, false);
-    }
+    check_checks(log, &ctx, &CheckSettings::default())
+        .into_iter()
+        .map(|(_, report)| report)
+        .collect()
+}
 
-    embed
+/// Runs every check on a log, for tools that want the reports without a Discord embed
+pub fn check_checks_json(log: &str) -> serde_json::Value {
+    let ctx = get_environment_info(log);
+    check_checks(log, &ctx, &CheckSettings::default())
+        .into_iter()
+        .map(|(id, report)| {
+            let mut value = serde_json::to_value(report).expect("Serializing report");
+            value["id"] = id.into();
+            value
+        })
+        .collect()
 }
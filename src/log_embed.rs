@@ -0,0 +1,249 @@
+use std::collections::HashSet;
+
+use serenity::all::{CreateEmbed, CreateEmbedFooter};
+use tokio::time::Instant;
+
+use crate::{
+    config::Colors,
+    log_checking::{
+        checks::{check_checks, CheckReport, CheckSettings, Severity},
+        environment::get_environment_info,
+    },
+    log_upload,
+    stats::Stats,
+};
+
+// Discord's limits for embeds, lengths are in characters
+const MAX_FIELDS: usize = 25;
+const MAX_FIELD_NAME: usize = 256;
+const MAX_FIELD_VALUE: usize = 1024;
+/// Applies to all embeds of a message combined
+const MAX_EMBED_LENGTH: usize = 6000;
+/// Room kept in an embed for the issue summary and the note about checks that didn't fit
+const RESERVED_LENGTH: usize = 150;
+const MIN_EMBED_LENGTH: usize = 500;
+
+/// Cuts off text longer than `max` characters with an ellipsis, as Discord rejects the whole message otherwise
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(max - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+/// Only counts the reports that are shown, so the summary matches the fields below it
+fn severity_summary(shown: &[CheckReport]) -> String {
+    let count = |severity| shown.iter().filter(|r| r.severity == severity).count();
+    let issues = shown.len();
+
+    if issues == 0 {
+        return String::new();
+    }
+
+    let counts: Vec<_> = [
+        (count(Severity::High), "high"),
+        (count(Severity::Medium), "medium"),
+        (count(Severity::None), "info"),
+    ]
+    .iter()
+    .filter(|(count, _)| *count > 0)
+    .map(|(count, severity)| format!("{count} {severity}"))
+    .collect();
+    format!(
+        "**{issues} issue{} found:** {}\n\n",
+        if issues == 1 { "" } else { "s" },
+        counts.join(", ")
+    )
+}
+
+/// Discord limits the combined length of all embeds in a message, so the embeds of one response
+/// share this budget, together with the reports already shown for an earlier log
+pub struct EmbedBudget {
+    remaining: usize,
+    seen: HashSet<(String, String)>,
+}
+
+impl Default for EmbedBudget {
+    fn default() -> Self {
+        Self {
+            remaining: MAX_EMBED_LENGTH,
+            seen: HashSet::new(),
+        }
+    }
+}
+
+impl EmbedBudget {
+    /// Whether an embed with at least a title and a short description still fits
+    pub fn has_room(&self) -> bool {
+        self.remaining >= MIN_EMBED_LENGTH
+    }
+
+    /// Takes `length` characters from the budget if they fit
+    pub fn spend(&mut self, length: usize) -> bool {
+        if length > self.remaining {
+            return false;
+        }
+        self.remaining -= length;
+        true
+    }
+}
+
+fn length(text: &str) -> usize {
+    text.chars().count()
+}
+
+pub fn check_logs(
+    log: &log_upload::Log,
+    settings: &CheckSettings,
+    colors: &Colors,
+    stats: &Stats,
+    budget: &mut EmbedBudget,
+) -> CreateEmbed {
+    let start = Instant::now();
+    let ctx = get_environment_info(&log.content);
+    let checks: Vec<_> = check_checks(&log.content, &ctx, settings)
+        .into_iter()
+        .map(|(id, report)| {
+            stats.record_check(id);
+            report
+        })
+        .collect();
+    let severity = checks
+        .iter()
+        .map(|r| r.severity)
+        .max()
+        .unwrap_or(Severity::None);
+    let took = Instant::now() - start;
+    // Reports already shown for an earlier log of the same message are only mentioned
+    let found = checks.len();
+    let checks: Vec<_> = checks
+        .into_iter()
+        .filter(|report| {
+            budget
+                .seen
+                .insert((report.title.clone(), report.description.clone()))
+        })
+        .collect();
+    let repeated = match found - checks.len() {
+        0 => String::new(),
+        1 => "\n1 issue is the same as for a log above.\n".to_string(),
+        repeated => format!("\n{repeated} issues are the same as for a log above.\n"),
+    };
+
+    let title = log.kind.title_format(&log.name, &took);
+    let heading = if found == 0 {
+        match log.kind {
+            log_upload::LogType::Uploaded => {
+                "\n**No known issues detected**, the uploaded log is linked below.\n"
+            }
+            _ => "\n**No known issues detected.**\n",
+        }
+    } else if checks.is_empty() {
+        ""
+    } else if matches!(severity, Severity::None) {
+        "\n**More Information:**\n"
+    } else {
+        "\n**Potential Issues Detected:**\n"
+    };
+    let footer = log
+        .truncated
+        .then_some("This log was too long, so only its end was scanned.");
+
+    // The summary and the note about checks that didn't fit are short, so room is kept for them
+    let mut used = length(&title)
+        + length(&format!("{ctx}{repeated}{heading}"))
+        + footer.map_or(0, length)
+        + RESERVED_LENGTH;
+    let mut fields = vec![];
+    for ele in &checks {
+        let name = truncate(&format!("- {}", &ele.title), MAX_FIELD_NAME);
+        let value = truncate(&ele.description, MAX_FIELD_VALUE);
+        let field_length = length(&name) + length(&value);
+        if used + field_length > budget.remaining || fields.len() == MAX_FIELDS {
+            break;
+        }
+        used += field_length;
+        fields.push((name, value));
+    }
+    // The note about the hidden checks needs a field too
+    if fields.len() < checks.len() && fields.len() == MAX_FIELDS {
+        fields.pop();
+    }
+    let hidden = checks.len() - fields.len();
+
+    let description = format!(
+        "{}{ctx}{repeated}{heading}",
+        severity_summary(&checks[..fields.len()])
+    );
+    let mut embed = CreateEmbed::new()
+        .title(&title)
+        .color(colors.get(severity))
+        .description(&description);
+    let mut total = length(&title) + length(&description) + footer.map_or(0, length);
+    for (name, value) in fields {
+        total += length(&name) + length(&value);
+        embed = embed.field(name, value, false);
+    }
+    if hidden > 0 {
+        let name = format!("- {hidden} more");
+        let value = "Too many issues to show them all, fix the ones above first.";
+        total += length(&name) + length(value);
+        embed = embed.field(name, value, false);
+    }
+    if let Some(footer) = footer {
+        embed = embed.footer(CreateEmbedFooter::new(footer));
+    }
+
+    budget.remaining = budget.remaining.saturating_sub(total);
+    embed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn severity_summary_counts_every_severity() {
+        let report = |severity| CheckReport {
+            title: "Title".to_string(),
+            description: "Description".to_string(),
+            severity,
+        };
+        let shown = [
+            report(Severity::High),
+            report(Severity::Medium),
+            report(Severity::None),
+        ];
+        assert_eq!(
+            severity_summary(&shown),
+            "**3 issues found:** 1 high, 1 medium, 1 info\n\n"
+        );
+        assert_eq!(
+            severity_summary(&shown[..1]),
+            "**1 issue found:** 1 high\n\n"
+        );
+        assert_eq!(severity_summary(&[]), "");
+    }
+
+    #[test]
+    fn truncate_adds_an_ellipsis() {
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(truncate("ääääääää", 5), "ääää…");
+        assert_eq!(
+            length(&truncate(&"x".repeat(2000), MAX_FIELD_VALUE)),
+            MAX_FIELD_VALUE
+        );
+    }
+
+    #[test]
+    fn embed_budget_is_shared() {
+        let mut budget = EmbedBudget::default();
+        assert!(budget.spend(MAX_EMBED_LENGTH - MIN_EMBED_LENGTH));
+        assert!(budget.has_room());
+        assert!(!budget.spend(MIN_EMBED_LENGTH + 1));
+        assert!(budget.spend(1));
+        assert!(!budget.has_room());
+    }
+}
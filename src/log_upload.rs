@@ -1,30 +1,43 @@
 use std::{
-    io::{Cursor, ErrorKind, Read},
+    future::Future,
+    io::{Cursor, Read},
     path::Path,
+    sync::{atomic::Ordering, LazyLock},
     time::Duration,
 };
 
 use anyhow::Result;
-use flate2::read::GzDecoder;
+use flate2::read::MultiGzDecoder;
+use futures::{stream, StreamExt};
+use mental_instability_bot::cached_regex;
 use regex::Regex;
+use reqwest::{header::RANGE, StatusCode};
 use serde::{Deserialize, Serialize};
 use serenity::{
-    all::{Attachment, Message},
+    all::{Attachment, GuildId, Message, UserId},
     builder::{CreateActionRow, CreateButton, CreateEmbed},
 };
+use zip::ZipArchive;
 
 use serenity::client::Context;
 
 use crate::{
+    config::{Colors, Config},
     constants::{MCLOGS_API_BASE_URL, MCLOGS_BASE_URL},
-    get_config,
-    log_checking::check_logs,
+    cooldown::{UploadLimitsData, DEFAULT_UPLOADS_PER_MINUTE},
+    http::HTTP_CLIENT,
+    log_checking::checks::{
+        CheckSettings, Severity, DEFAULT_LARGE_MODPACK_THRESHOLD, DEFAULT_MEMORY_PER_MOD_MB,
+    },
+    log_embed::{check_logs, EmbedBudget},
+    stats::{get_stats, Stats},
+    ConfigData,
 };
 
 #[derive(Deserialize, Clone)]
 struct UploadData {
     url: Option<String>,
-    _error: Option<String>,
+    error: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -32,11 +45,21 @@ struct LogUpload<'a> {
     content: &'a str,
 }
 
-type Log = (String, LogType, String, String);
+pub(crate) type LogResponse = (String, Vec<CreateEmbed>, Vec<CreateActionRow>);
+
+pub(crate) struct Log {
+    pub(crate) name: String,
+    pub(crate) kind: LogType,
+    url: Option<String>,
+    pub(crate) content: String,
+    pub(crate) truncated: bool,
+}
 
 pub(crate) enum LogType {
     Uploaded,
     Downloaded,
+    /// mclo.gs refused the upload with the given error
+    Rejected(String),
 }
 
 impl LogType {
@@ -44,6 +67,66 @@ impl LogType {
         match self {
             Self::Uploaded => format!("Uploaded {name} in {took:?}"),
             Self::Downloaded => format!("Scanned {name} in {took:?}"),
+            Self::Rejected(_) => format!("Couldn't upload {name}"),
+        }
+    }
+}
+
+const DEFAULT_MAX_LOG_BYTES: u64 = 10_000_000;
+const DEFAULT_TRUNCATE_TO: usize = 1_000_000;
+
+#[derive(Clone, Copy)]
+struct LogLimits {
+    max_size: u64,
+    truncate_to: usize,
+}
+
+impl LogLimits {
+    fn from_config(config: &Config) -> Self {
+        Self {
+            max_size: config.max_log_bytes.unwrap_or(DEFAULT_MAX_LOG_BYTES),
+            truncate_to: config.truncate_to.unwrap_or(DEFAULT_TRUNCATE_TO),
+        }
+    }
+}
+
+/// What a scan needs from the config, copied out so the config isn't locked while uploading
+struct ScanConfig {
+    settings: CheckSettings,
+    colors: Colors,
+    limits: LogLimits,
+    paste_services: Vec<String>,
+    log_extensions: Vec<String>,
+    uploads_per_minute: u32,
+}
+
+impl ScanConfig {
+    async fn resolve(ctx: &Context, guild: Option<GuildId>) -> Self {
+        let data = ctx.data.read().await;
+        let config = data
+            .get::<ConfigData>()
+            .expect("No config?")
+            .for_guild(guild);
+        let global = config.global();
+
+        Self {
+            settings: CheckSettings {
+                disabled_checks: config.disabled_checks().to_vec(),
+                large_modpack_threshold: global
+                    .large_modpack_threshold
+                    .unwrap_or(DEFAULT_LARGE_MODPACK_THRESHOLD),
+                memory_per_mod_mb: global
+                    .memory_per_mod_mb
+                    .unwrap_or(DEFAULT_MEMORY_PER_MOD_MB),
+                dynamic_checks: global.dynamic_checks.clone(),
+            },
+            colors: config.colors(),
+            limits: LogLimits::from_config(global),
+            paste_services: global.paste_services.clone(),
+            log_extensions: global.log_extensions.clone(),
+            uploads_per_minute: global
+                .uploads_per_minute
+                .unwrap_or(DEFAULT_UPLOADS_PER_MINUTE),
         }
     }
 }
@@ -51,126 +134,867 @@ impl LogType {
 pub(crate) async fn check_for_logs(
     ctx: &Context,
     message: &Message,
+    requester: UserId,
     all: bool,
-) -> Result<Option<(&'static str, Vec<CreateEmbed>, Vec<CreateActionRow>)>> {
-    if let Some(file_extensions) = &get_config!(ctx).log_extensions {
-        let attachments: Vec<_> = message
-            .attachments
-            .iter()
-            .filter(|attachment| all || is_valid_log(attachment, file_extensions))
-            .collect();
+) -> Result<Option<LogResponse>> {
+    let config = ScanConfig::resolve(ctx, message.guild_id).await;
+    let limits = config.limits;
+    let file_extensions = &config.log_extensions;
+    let stats = get_stats(ctx).await;
+
+    let mut attachments = vec![];
+    for attachment in &message.attachments {
+        if all || is_valid_log(attachment, file_extensions, limits).await {
+            attachments.push(attachment);
+        }
+    }
 
-        let mut logs: Vec<Log> = upload_log_files(&attachments).await?;
-        logs.append(&mut check_pre_uploaded_logs(&message.content).await?);
+    let (files, read_error) = read_log_files(&attachments, file_extensions, limits).await;
 
-        if logs.is_empty() {
-            return Ok(None);
+    // Only the files that are really uploaded count, and against whoever asked for the scan
+    if !files.is_empty()
+        && let Err(wait) = ctx
+            .data
+            .read()
+            .await
+            .get::<UploadLimitsData>()
+            .expect("No upload limits?")
+            .try_take(requester, files.len() as u32, config.uploads_per_minute)
+    {
+        return Ok(Some((
+            format!(
+                "You're uploading logs too quickly, try again in {} seconds.",
+                wait.as_secs() + 1
+            ),
+            vec![],
+            vec![],
+        )));
+    }
+
+    let mut logs: Vec<Log> = upload_log_files(files, read_error, limits, &stats).await?;
+    logs.append(
+        &mut check_pre_uploaded_logs(&message.content, &config.paste_services, limits).await?,
+    );
+
+    if logs.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(log_response(
+        &logs,
+        &config.settings,
+        &config.colors,
+        &stats,
+    )))
+}
+
+/// The attachments and links a message's logs come from, which can be compared across edits
+pub(crate) fn log_sources(message: &Message, paste_services: &[String]) -> Vec<String> {
+    let attachments = message
+        .attachments
+        .iter()
+        .map(|attachment| attachment.id.to_string());
+    let mclogs_urls = find_mclogs_urls(&message.content)
+        .into_iter()
+        .map(|id| format!("{MCLOGS_BASE_URL}/{id}"));
+    let paste_urls = find_paste_urls(&message.content, paste_services)
+        .into_iter()
+        .map(|(_, _, url)| url);
+
+    attachments.chain(mclogs_urls).chain(paste_urls).collect()
+}
+
+pub(crate) async fn check_for_logs_at_url(
+    ctx: &Context,
+    url: &str,
+    guild: Option<GuildId>,
+) -> Result<Option<LogResponse>> {
+    let config = ScanConfig::resolve(ctx, guild).await;
+    let stats = get_stats(ctx).await;
+
+    let logs = check_pre_uploaded_logs(url, &config.paste_services, config.limits).await?;
+
+    if logs.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(log_response(
+        &logs,
+        &config.settings,
+        &config.colors,
+        &stats,
+    )))
+}
+
+// Discord's limits for a single message
+const MAX_EMBEDS: usize = 10;
+const MAX_ACTION_ROWS: usize = 5;
+const MAX_BUTTONS_PER_ROW: usize = 5;
+
+fn log_response(
+    logs: &[Log],
+    settings: &CheckSettings,
+    colors: &Colors,
+    stats: &Stats,
+) -> LogResponse {
+    let mut budget = EmbedBudget::default();
+    let mut embeds = vec![];
+    for log in logs.iter().take(MAX_EMBEDS) {
+        if !budget.has_room() {
+            break;
+        }
+        if let LogType::Rejected(error) = &log.kind {
+            let title = log.kind.title_format(&log.name, &Duration::ZERO);
+            let description = format!("mclo.gs didn't accept this log: {error}");
+            if !budget.spend(title.chars().count() + description.chars().count()) {
+                break;
+            }
+            embeds.push(
+                CreateEmbed::new()
+                    .title(title)
+                    .description(description)
+                    .color(colors.get(Severity::Medium)),
+            );
+            continue;
         }
 
-        let edit = (
-            "",
-            logs.iter()
-                .map(|(name, t, _, log)| check_logs(log, name, t))
-                .collect(),
-            vec![CreateActionRow::Buttons(
-                logs.iter()
-                    .map(|(name, _, url, _)| CreateButton::new_link(url).label(name))
-                    .collect(),
-            )],
-        );
+        stats.logs_scanned.fetch_add(1, Ordering::Relaxed);
+        embeds.push(check_logs(log, settings, colors, stats, &mut budget));
+    }
 
-        Ok(Some(edit))
+    // The buttons still link every uploaded log, even the ones without an embed
+    let content = if embeds.len() < logs.len() {
+        format!(
+            "Only the first {} logs were scanned, {} more were skipped.",
+            embeds.len(),
+            logs.len() - embeds.len()
+        )
     } else {
-        Ok(None)
+        String::new()
+    };
+
+    let buttons: Vec<_> = logs
+        .iter()
+        .filter_map(|log| Some(CreateButton::new_link(log.url.as_ref()?).label(&log.name)))
+        .take(MAX_ACTION_ROWS * MAX_BUTTONS_PER_ROW)
+        .collect();
+    let components = buttons
+        .chunks(MAX_BUTTONS_PER_ROW)
+        .map(|row| CreateActionRow::Buttons(row.to_vec()))
+        .collect();
+
+    (content, embeds, components)
+}
+
+/// Keeps the end of the log, where crashes usually are, starting on a full line
+fn truncate_log(mut log: String, limit: usize) -> (String, bool) {
+    if log.len() <= limit {
+        return (log, false);
+    }
+
+    let mut start = log.len() - limit;
+    while !log.is_char_boundary(start) {
+        start += 1;
     }
+    if let Some(newline) = log[start..].find('\n') {
+        start += newline + 1;
+    }
+
+    (log.split_off(start), true)
 }
 
-fn is_valid_log<T: AsRef<str>>(attachment: &Attachment, allowed_extensions: &[T]) -> bool {
-    attachment.size < 1_000_000
-        && (allowed_extensions
-            .iter()
-            .any(|extension| attachment.filename.ends_with(extension.as_ref())))
+async fn is_valid_log<T: AsRef<str>>(
+    attachment: &Attachment,
+    allowed_extensions: &[T],
+    limits: LogLimits,
+) -> bool {
+    if u64::from(attachment.size) >= limits.max_size {
+        return false;
+    }
+    if has_log_extension(&attachment.filename, allowed_extensions) {
+        return true;
+    }
+
+    // Crash reports are saved as `.txt`, so other text files only count if they start like one
+    is_txt(&attachment.filename)
+        && download_start(&attachment.url)
+            .await
+            .is_ok_and(|start| is_crash_report(&decode_log(start)))
 }
 
-async fn upload_log_files(attachments: &[&Attachment]) -> Result<Vec<Log>> {
-    let mut responses = vec![];
+fn is_txt(filename: &str) -> bool {
+    Path::new(filename)
+        .extension()
+        .map_or(false, |ext| ext.eq_ignore_ascii_case("txt"))
+}
 
-    for attachment in attachments {
-        let data = if Path::new(&attachment.filename)
-            .extension()
-            .map_or(false, |ext| ext.eq_ignore_ascii_case("gz"))
-        {
-            let mut reader = GzDecoder::new(Cursor::new(
-                attachment
-                    .download()
-                    .await
-                    .map_err(|e| std::io::Error::new(ErrorKind::Other, e))?,
-            ));
-
-            let mut buf = Vec::new();
-            reader.read_to_end(&mut buf)?;
-            buf
-        } else {
-            attachment.download().await?
+fn is_crash_report(log: &str) -> bool {
+    log.trim_start()
+        .starts_with("---- Minecraft Crash Report ----")
+}
+
+/// Compressed logs count if the name without `.gz` has an allowed extension too
+fn has_log_extension<T: AsRef<str>>(filename: &str, allowed_extensions: &[T]) -> bool {
+    let stripped = strip_gz(filename);
+    allowed_extensions.iter().any(|extension| {
+        filename.ends_with(extension.as_ref())
+            || stripped.is_some_and(|name| name.ends_with(extension.as_ref()))
+    })
+}
+
+/// Windows tools sometimes save logs as UTF-16 or with a byte order mark
+pub(crate) fn decode_log(data: Vec<u8>) -> String {
+    let utf16 = |data: &[u8], from_bytes: fn([u8; 2]) -> u16| {
+        let units: Vec<u16> = data
+            .chunks_exact(2)
+            .map(|pair| from_bytes([pair[0], pair[1]]))
+            .collect();
+        String::from_utf16_lossy(&units)
+    };
+
+    match data.as_slice() {
+        [0xEF, 0xBB, 0xBF, rest @ ..] => String::from_utf8_lossy(rest).into_owned(),
+        [0xFF, 0xFE, rest @ ..] => utf16(rest, u16::from_le_bytes),
+        [0xFE, 0xFF, rest @ ..] => utf16(rest, u16::from_be_bytes),
+        // ASCII text in UTF-16LE without a byte order mark
+        [_, 0, _, 0, ..] => utf16(&data, u16::from_le_bytes),
+        _ => String::from_utf8(data)
+            .unwrap_or_else(|err| String::from_utf8_lossy(err.as_bytes()).into_owned()),
+    }
+}
+
+fn is_zip(filename: &str) -> bool {
+    Path::new(filename)
+        .extension()
+        .map_or(false, |ext| ext.eq_ignore_ascii_case("zip"))
+}
+
+fn strip_gz(filename: &str) -> Option<&str> {
+    let (name, ext) = filename.rsplit_once('.')?;
+    ext.eq_ignore_ascii_case("gz").then_some(name)
+}
+
+fn extract_zip_logs<T: AsRef<str>>(
+    data: Vec<u8>,
+    allowed_extensions: &[T],
+    limits: LogLimits,
+) -> Result<Vec<(String, String)>> {
+    let mut archive = ZipArchive::new(Cursor::new(data))?;
+    let mut logs = vec![];
+    let mut extracted = 0;
+
+    for i in 0..archive.len() {
+        let entry = archive.by_index(i)?;
+        let name = entry
+            .name()
+            .rsplit('/')
+            .next()
+            .unwrap_or(entry.name())
+            .to_string();
+        if !entry.is_file() || !has_log_extension(&name, allowed_extensions) {
+            continue;
+        }
+
+        let mut buf = Vec::new();
+        entry.take(limits.max_size).read_to_end(&mut buf)?;
+        // Rotated logs in launcher exports are compressed on their own
+        let (name, buf) = match strip_gz(&name) {
+            Some(stripped) => (stripped.to_string(), decompress_gz(buf, limits)),
+            None => (name, buf),
         };
-        let log = String::from_utf8_lossy(&data);
 
-        let data = upload(&log).await?;
+        // Guards against zip bombs, as every entry can decompress to the size limit
+        extracted += buf.len() as u64;
+        if extracted > 5 * limits.max_size {
+            break;
+        }
+
+        logs.push((name, decode_log(buf)));
+    }
+
+    Ok(logs)
+}
+
+/// Reads every member of rotated logs, and falls back to the raw bytes if nothing can be decoded
+fn decompress_gz(data: Vec<u8>, limits: LogLimits) -> Vec<u8> {
+    let mut buf = Vec::new();
+    let result = MultiGzDecoder::new(Cursor::new(&data))
+        .take(limits.max_size)
+        .read_to_end(&mut buf);
+
+    match result {
+        Ok(_) => buf,
+        // Trailing garbage after valid members is only an error at the end
+        Err(_) if !buf.is_empty() => buf,
+        Err(err) => {
+            println!("Failed to decompress log, using it as is: {err}");
+            data
+        }
+    }
+}
+
+/// Downloads the attachments, unpacking archives and compressed logs, and keeps going past
+/// the ones that fail
+async fn read_log_files<T: AsRef<str>>(
+    attachments: &[&Attachment],
+    allowed_extensions: &[T],
+    limits: LogLimits,
+) -> (Vec<(String, String)>, Option<anyhow::Error>) {
+    let mut files = vec![];
+    let mut last_error = None;
+
+    for attachment in attachments {
+        match read_log_file(attachment, allowed_extensions, limits).await {
+            Ok(mut logs) => files.append(&mut logs),
+            Err(err) => {
+                println!("Failed to read {}: {err}", attachment.filename);
+                last_error = Some(err);
+            }
+        }
+    }
+
+    (files, last_error)
+}
+
+async fn read_log_file<T: AsRef<str>>(
+    attachment: &Attachment,
+    allowed_extensions: &[T],
+    limits: LogLimits,
+) -> Result<Vec<(String, String)>> {
+    let data = attachment.download().await?;
+    if is_zip(&attachment.filename) {
+        return extract_zip_logs(data, allowed_extensions, limits);
+    }
+
+    let (name, data) = match strip_gz(&attachment.filename) {
+        Some(name) => (name, decompress_gz(data, limits)),
+        None => (attachment.filename.as_str(), data),
+    };
+    Ok(vec![(name.to_string(), decode_log(data))])
+}
+
+/// Files that couldn't be read only fail the upload if nothing else could be uploaded either
+async fn upload_log_files(
+    files: Vec<(String, String)>,
+    read_error: Option<anyhow::Error>,
+    limits: LogLimits,
+    stats: &Stats,
+) -> Result<Vec<Log>> {
+    // Buffered keeps the order of the logs while uploading a few at a time
+    let uploads: Vec<_> = stream::iter(files)
+        .map(|(name, log)| async move {
+            let (content, truncated) = truncate_log(log, limits.truncate_to);
+            let result = upload(&content).await;
+            (name, content, truncated, result)
+        })
+        .buffered(MAX_CONCURRENT_UPLOADS)
+        .collect()
+        .await;
+
+    let mut responses = vec![];
+    let mut last_error = read_error;
 
-        if let Some(url) = data.url {
-            responses.push((
-                attachment.filename.clone(),
-                LogType::Uploaded,
-                url,
-                log.into_owned(),
-            ));
+    for (name, content, truncated, result) in uploads {
+        match result {
+            Ok(UploadData { url: Some(url), .. }) => {
+                responses.push(Log {
+                    name,
+                    kind: LogType::Uploaded,
+                    url: Some(url),
+                    content,
+                    truncated,
+                });
+            }
+            Ok(UploadData {
+                error: Some(error), ..
+            }) => {
+                stats.upload_failures.fetch_add(1, Ordering::Relaxed);
+                responses.push(Log {
+                    name,
+                    kind: LogType::Rejected(error),
+                    url: None,
+                    content: String::new(),
+                    truncated,
+                });
+            }
+            Ok(_) => {}
+            Err(err) => {
+                println!("Failed to upload {name}: {err}");
+                stats.upload_failures.fetch_add(1, Ordering::Relaxed);
+                last_error = Some(err);
+            }
         }
     }
 
-    Ok(responses)
+    partial_result(responses, last_error)
 }
 
-async fn check_pre_uploaded_logs(message_content: &str) -> Result<Vec<Log>> {
+async fn check_pre_uploaded_logs(
+    message_content: &str,
+    paste_services: &[String],
+    limits: LogLimits,
+) -> Result<Vec<Log>> {
     let mut responses = vec![];
+    let mut last_error = None;
 
-    for id in find_mclogs_urls(message_content) {
-        let log_data = download(&id).await?;
-        let url = format!("{MCLOGS_BASE_URL}/{id}");
-        responses.push((id, LogType::Downloaded, url, log_data));
+    let mclogs_urls = find_mclogs_urls(message_content).into_iter().map(|id| {
+        (
+            format!("{MCLOGS_API_BASE_URL}/1/raw/{id}"),
+            format!("{MCLOGS_BASE_URL}/{id}"),
+            id,
+        )
+    });
+    let paste_urls = find_paste_urls(message_content, paste_services)
+        .into_iter()
+        .map(|(service, id, url)| ((service.raw_url)(&id), url, id));
+
+    for (raw_url, url, id) in mclogs_urls.chain(paste_urls) {
+        match download(&raw_url).await {
+            Ok(Some(log_data)) => {
+                let (content, truncated) = truncate_log(log_data, limits.truncate_to);
+                responses.push(Log {
+                    name: id,
+                    kind: LogType::Downloaded,
+                    url: Some(url),
+                    content,
+                    truncated,
+                });
+            }
+            Ok(None) => {}
+            Err(err) => {
+                println!("Failed to download {url}: {err}");
+                last_error = Some(err);
+            }
+        }
     }
 
-    Ok(responses)
+    partial_result(responses, last_error)
+}
+
+/// Keeps whatever logs succeeded, only failing if every one of them did
+fn partial_result(responses: Vec<Log>, last_error: Option<anyhow::Error>) -> Result<Vec<Log>> {
+    match last_error {
+        Some(err) if responses.is_empty() => Err(err),
+        _ => Ok(responses),
+    }
 }
 
 fn find_mclogs_urls(message_content: &str) -> Vec<String> {
-    let regex = Regex::new(r"https:\/\/mclo\.gs\/([a-zA-Z0-9]+)").unwrap();
+    let regex = cached_regex!(r"https:\/\/mclo\.gs\/([a-zA-Z0-9]+)");
 
-    regex
-        .captures_iter(message_content)
-        .map(|caps| caps.get(1).expect("Regex err").as_str().to_string())
-        .collect()
+    let mut ids: Vec<String> = vec![];
+    for caps in regex.captures_iter(message_content) {
+        let id = caps.get(1).expect("Regex err").as_str();
+        if !ids.iter().any(|existing| existing == id) {
+            ids.push(id.to_string());
+        }
+    }
+    ids
+}
+
+struct PasteService {
+    name: &'static str,
+    /// Compiled once per service, the first time it's enabled
+    regex: LazyLock<Regex>,
+    raw_url: fn(&str) -> String,
+}
+
+static PASTE_SERVICES: [PasteService; 4] = [
+    PasteService {
+        name: "pastebin",
+        regex: LazyLock::new(|| {
+            Regex::new(r"https:\/\/pastebin\.com\/(?:raw\/)?([a-zA-Z0-9]+)").unwrap()
+        }),
+        raw_url: |id| format!("https://pastebin.com/raw/{id}"),
+    },
+    PasteService {
+        name: "hastebin",
+        regex: LazyLock::new(|| {
+            Regex::new(r"https:\/\/hastebin\.com\/(?:share\/|raw\/)?([a-zA-Z0-9]+)").unwrap()
+        }),
+        raw_url: |id| format!("https://hastebin.com/raw/{id}"),
+    },
+    PasteService {
+        name: "0x0",
+        regex: LazyLock::new(|| {
+            Regex::new(r"https:\/\/0x0\.st\/([a-zA-Z0-9_\-]+(?:\.[a-zA-Z0-9]+)?)").unwrap()
+        }),
+        raw_url: |id| format!("https://0x0.st/{id}"),
+    },
+    PasteService {
+        name: "paste.ee",
+        regex: LazyLock::new(|| Regex::new(r"https:\/\/paste\.ee\/[pr]\/([a-zA-Z0-9]+)").unwrap()),
+        raw_url: |id| format!("https://paste.ee/r/{id}"),
+    },
+];
+
+fn find_paste_urls(
+    message_content: &str,
+    enabled: &[String],
+) -> Vec<(&'static PasteService, String, String)> {
+    let mut urls: Vec<(&'static PasteService, String, String)> = vec![];
+
+    for service in PASTE_SERVICES
+        .iter()
+        .filter(|service| enabled.iter().any(|name| name == service.name))
+    {
+        for caps in service.regex.captures_iter(message_content) {
+            let id = caps.get(1).expect("Regex err").as_str();
+            if !urls
+                .iter()
+                .any(|(s, existing, _)| s.name == service.name && existing == id)
+            {
+                urls.push((
+                    service,
+                    id.to_string(),
+                    caps.get(0).expect("Regex err").as_str().to_string(),
+                ));
+            }
+        }
+    }
+
+    urls
+}
+
+const RETRY_ATTEMPTS: u32 = 3;
+/// Enough to speed up messages with several logs without hammering mclo.gs
+const MAX_CONCURRENT_UPLOADS: usize = 3;
+
+fn is_transient(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<reqwest::Error>().map_or(false, |err| {
+        err.is_timeout()
+            || err.is_connect()
+            || err
+                .status()
+                .map_or(false, |status| status.is_server_error())
+    })
+}
+
+async fn with_retries<T, F, Fut>(mut request: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut delay = Duration::from_millis(500);
+    let mut attempt = 1;
+
+    loop {
+        match request().await {
+            Err(err) if attempt < RETRY_ATTEMPTS && is_transient(&err) => {
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
 }
 
 async fn upload(log: &str) -> Result<UploadData> {
-    let client = reqwest::Client::new();
+    let body = &serde_urlencoded::to_string(LogUpload { content: log })?;
 
-    Ok(client
-        .post(format!("{MCLOGS_API_BASE_URL}/1/log"))
-        .header("Content-Type", "application/x-www-form-urlencoded")
-        .body(serde_urlencoded::to_string(LogUpload { content: log })?)
-        .send()
-        .await?
-        .json()
-        .await?)
+    with_retries(move || async move {
+        let response = HTTP_CLIENT
+            .post(format!("{MCLOGS_API_BASE_URL}/1/log"))
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(body.clone())
+            .send()
+            .await?;
+        if response.status().is_server_error() {
+            response.error_for_status_ref()?;
+        }
+
+        Ok(response.json().await?)
+    })
+    .await
 }
 
-async fn download(id: &str) -> Result<String> {
-    let client = reqwest::Client::new();
+/// Enough for the header of a crash report, even with leading whitespace or in UTF-16
+const SNIFF_BYTES: usize = 256;
 
-    Ok(client
-        .get(format!("{MCLOGS_API_BASE_URL}/1/raw/{id}"))
+async fn download_start(url: &str) -> Result<Vec<u8>> {
+    let response = HTTP_CLIENT
+        .get(url)
+        .header(RANGE, format!("bytes=0-{}", SNIFF_BYTES - 1))
         .send()
         .await?
-        .text()
-        .await?)
+        .error_for_status()?;
+
+    let mut start = response.bytes().await?.to_vec();
+    start.truncate(SNIFF_BYTES);
+    Ok(start)
+}
+
+async fn download(url: &str) -> Result<Option<String>> {
+    with_retries(move || async move {
+        let response = HTTP_CLIENT.get(url).send().await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        Ok(Some(response.error_for_status()?.text().await?))
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::{write::GzEncoder, Compression};
+    use zip::{write::SimpleFileOptions, ZipWriter};
+
+    use super::*;
+
+    const LIMITS: LogLimits = LogLimits {
+        max_size: 1_000_000,
+        truncate_to: 1_000,
+    };
+
+    fn attachment(filename: &str, size: u64) -> Attachment {
+        serde_json::from_value(serde_json::json!({
+            "id": "1",
+            "filename": filename,
+            "size": size,
+            "url": format!("https://cdn.discordapp.com/attachments/1/1/{filename}"),
+            "proxy_url": format!("https://media.discordapp.net/attachments/1/1/{filename}"),
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn only_allowed_extensions_are_logs() {
+        let allowed = [".log"];
+        assert!(is_valid_log(&attachment("latest.log", 100), &allowed, LIMITS).await);
+        assert!(!is_valid_log(&attachment("logs.zip", 100), &allowed, LIMITS).await);
+        assert!(is_valid_log(&attachment("logs.zip", 100), &[".zip"], LIMITS).await);
+    }
+
+    #[test]
+    fn crash_report_header_is_recognized() {
+        let mut utf16 = vec![0xFF, 0xFE];
+        for unit in "---- Minecraft Crash Report ----\n// Oops.".encode_utf16() {
+            utf16.extend(unit.to_le_bytes());
+        }
+        assert!(is_crash_report(&decode_log(utf16)));
+        assert!(is_crash_report("\n---- Minecraft Crash Report ----\n"));
+        assert!(!is_crash_report("shopping list:\n- eggs"));
+    }
+
+    #[test]
+    fn failed_files_only_fail_when_nothing_else_worked() {
+        let log = Log {
+            name: "latest.log".to_string(),
+            kind: LogType::Uploaded,
+            url: None,
+            content: String::new(),
+            truncated: false,
+        };
+        let logs = partial_result(vec![log], Some(anyhow::anyhow!("download failed")));
+        assert_eq!(logs.unwrap().len(), 1);
+        assert!(partial_result(vec![], Some(anyhow::anyhow!("download failed"))).is_err());
+    }
+
+    #[test]
+    fn paste_urls_only_for_enabled_services() {
+        let message = "https://pastebin.com/AbC123 https://paste.ee/p/XyZ";
+        let urls = find_paste_urls(message, &["pastebin".to_string()]);
+        assert_eq!(urls.len(), 1);
+        assert_eq!(urls[0].0.name, "pastebin");
+        assert_eq!(
+            (urls[0].0.raw_url)(&urls[0].1),
+            "https://pastebin.com/raw/AbC123"
+        );
+    }
+
+    #[test]
+    fn mclogs_urls_are_deduplicated() {
+        let ids = find_mclogs_urls(
+            "https://mclo.gs/abc and https://mclo.gs/def, also https://mclo.gs/ghi https://mclo.gs/abc",
+        );
+        assert_eq!(ids, ["abc", "def", "ghi"]);
+    }
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn zip_logs_are_extracted() {
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = SimpleFileOptions::default();
+        zip.start_file("logs/latest.log", options).unwrap();
+        zip.write_all(b"latest log").unwrap();
+        zip.start_file("crash-reports/crash.txt", options).unwrap();
+        zip.write_all(b"crash report").unwrap();
+        zip.start_file("logs/2024-01-01-1.log.gz", options).unwrap();
+        zip.write_all(&gzip(b"rotated log")).unwrap();
+        zip.start_file("screenshots/image.png", options).unwrap();
+        zip.write_all(b"not a log").unwrap();
+        let data = zip.finish().unwrap().into_inner();
+
+        let logs = extract_zip_logs(data, &[".log", ".txt"], LIMITS).unwrap();
+        assert_eq!(
+            logs,
+            [
+                ("latest.log".to_string(), "latest log".to_string()),
+                ("crash.txt".to_string(), "crash report".to_string()),
+                ("2024-01-01-1.log".to_string(), "rotated log".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn truncate_log_keeps_the_end_on_a_full_line() {
+        let log = "first line\nsecond line\nthird line".to_string();
+        assert_eq!(
+            truncate_log(log.clone(), 15),
+            ("third line".to_string(), true)
+        );
+        assert_eq!(truncate_log(log.clone(), 100), (log, false));
+    }
+
+    fn uploaded(name: &str, content: &str) -> Log {
+        Log {
+            name: name.to_string(),
+            kind: LogType::Uploaded,
+            url: Some(format!("https://mclo.gs/{name}")),
+            content: content.to_string(),
+            truncated: false,
+        }
+    }
+
+    #[test]
+    fn log_response_respects_discord_limits() {
+        let logs: Vec<_> = (0..30)
+            .map(|i| uploaded(&format!("log-{i}.log"), "Failed to verify username!"))
+            .collect();
+        let (content, embeds, components) = log_response(
+            &logs,
+            &CheckSettings::default(),
+            &Colors::default(),
+            &Stats::new(&[]),
+        );
+
+        assert_eq!(embeds.len(), MAX_EMBEDS);
+        assert_eq!(
+            content,
+            "Only the first 10 logs were scanned, 20 more were skipped."
+        );
+        assert_eq!(components.len(), MAX_ACTION_ROWS);
+        assert!(components.iter().all(|row| matches!(
+            row,
+            CreateActionRow::Buttons(buttons) if buttons.len() <= MAX_BUTTONS_PER_ROW
+        )));
+    }
+
+    #[tokio::test]
+    async fn attachments_over_the_size_limit_are_skipped() {
+        let limits = LogLimits {
+            max_size: 1000,
+            ..LIMITS
+        };
+        assert!(is_valid_log(&attachment("latest.log", 999), &[".log"], limits).await);
+        assert!(!is_valid_log(&attachment("latest.log", 1001), &[".log"], limits).await);
+    }
+
+    #[test]
+    fn gz_logs_match_the_inner_extension() {
+        assert!(has_log_extension("latest.log.gz", &[".log"]));
+        assert!(has_log_extension("2024-01-01-1.log.gz", &[".log"]));
+        assert!(!has_log_extension("latest.log.gz", &[".txt"]));
+        assert!(!has_log_extension("image.png", &[".log", ".txt"]));
+    }
+
+    #[test]
+    fn log_response_uses_configured_colors() {
+        let logs = [uploaded("latest.log", "Couldn't load chunk [1, 2]\n")];
+        let colors = Colors {
+            high: Some(0x123456),
+            ..Colors::default()
+        };
+        let (_, embeds, _) =
+            log_response(&logs, &CheckSettings::default(), &colors, &Stats::new(&[]));
+        assert_eq!(serde_json::to_value(&embeds[0]).unwrap()["color"], 0x123456);
+    }
+
+    #[test]
+    fn decompress_gz_reads_every_member() {
+        let mut data = gzip(b"first member\n");
+        data.extend(gzip(b"second member\n"));
+        assert_eq!(
+            decompress_gz(data, LIMITS),
+            b"first member\nsecond member\n"
+        );
+    }
+
+    #[test]
+    fn decompress_gz_falls_back_to_raw_bytes() {
+        assert_eq!(decompress_gz(b"not gzip".to_vec(), LIMITS), b"not gzip");
+    }
+
+    #[test]
+    fn decode_log_handles_byte_order_marks() {
+        assert_eq!(decode_log(b"\xEF\xBB\xBFlog".to_vec()), "log");
+        assert_eq!(decode_log(b"\xFF\xFEl\0o\0g\0".to_vec()), "log");
+        assert_eq!(decode_log(b"\xFE\xFF\0l\0o\0g".to_vec()), "log");
+        assert_eq!(decode_log(b"l\0o\0g\0".to_vec()), "log");
+        assert_eq!(decode_log(b"log \xFF".to_vec()), "log \u{FFFD}");
+    }
+
+    /// Everything Discord counts towards the length limit of embeds
+    fn embed_length(embed: &CreateEmbed) -> usize {
+        let embed = serde_json::to_value(embed).unwrap();
+        let text =
+            |value: &serde_json::Value| value.as_str().map_or(0, |text| text.chars().count());
+        text(&embed["title"])
+            + text(&embed["description"])
+            + text(&embed["footer"]["text"])
+            + embed["fields"].as_array().map_or(0, |fields| {
+                fields
+                    .iter()
+                    .map(|field| text(&field["name"]) + text(&field["value"]))
+                    .sum()
+            })
+    }
+
+    #[test]
+    fn log_response_respects_the_total_embed_length() {
+        let logs: Vec<_> = (0..10)
+            .map(|i| {
+                let content = format!("---- Minecraft Crash Report ----\n// Oops.\n\nTime: 2024-01-01 12:00:00\nDescription: Crash {i}\n\njava.lang.RuntimeException: {}\n", "x".repeat(2000));
+                uploaded(&format!("crash-{i}.txt"), &content)
+            })
+            .collect();
+        let (_, embeds, _) = log_response(
+            &logs,
+            &CheckSettings::default(),
+            &Colors::default(),
+            &Stats::new(&[]),
+        );
+        assert!(!embeds.is_empty());
+        assert!(embeds.iter().map(embed_length).sum::<usize>() <= 6000);
+    }
+
+    #[test]
+    fn log_response_deduplicates_reports() {
+        let logs = [
+            uploaded("latest.log", "Couldn't load chunk [1, 2]\n"),
+            uploaded("debug.log", "Couldn't load chunk [1, 2]\n"),
+        ];
+        let (_, embeds, _) = log_response(
+            &logs,
+            &CheckSettings::default(),
+            &Colors::default(),
+            &Stats::new(&[]),
+        );
+        let second = serde_json::to_value(&embeds[1]).unwrap();
+        assert!(second["description"]
+            .as_str()
+            .unwrap()
+            .contains("1 issue is the same as for a log above."));
+        assert!(second["fields"].as_array().map_or(true, Vec::is_empty));
+    }
 }
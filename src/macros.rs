@@ -7,4 +7,7 @@ macro_rules! get_config {
             .get::<$crate::ConfigData>()
             .expect("No config?")
     }};
+    ($arg:expr, $guild:expr) => {{
+        $crate::get_config!($arg).for_guild($guild)
+    }};
 }
@@ -1,23 +1,50 @@
 #![feature(let_chains)]
 #![feature(async_fn_traits)]
 
+mod cli;
 mod commands;
 mod config;
 mod constants;
-mod log_checking;
+mod cooldown;
+mod health;
+mod http;
+mod log_embed;
 mod log_upload;
 mod macros;
+mod quotes;
+mod stats;
 
-use std::fs;
+use std::process;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use config::Config;
-use log_upload::check_for_logs;
+use config::{
+    load_config, load_guild_overrides, Config, CONFIG_PATH, EXAMPLE_CONFIG, GUILD_CONFIG_PATH,
+};
+use constants::RESCAN_EMOJI;
+use cooldown::{
+    CooldownData, Cooldowns, ScannedLogs, ScannedLogsData, UploadLimits, UploadLimitsData,
+    DEFAULT_SCAN_COOLDOWN,
+};
+use log_upload::{check_for_logs, log_sources, LogResponse};
+use mental_instability_bot::log_checking;
+use mental_instability_bot::log_checking::dynamic::{load_dynamic_checks, DYNAMIC_CHECKS_PATH};
 use poise::FrameworkOptions;
+use quotes::{QuoteStore, QuotesData, DEFAULT_QUOTES_DB};
+use serenity::all::AutoArchiveDuration;
+use serenity::all::ChannelId;
 use serenity::all::CreateMessage;
+use serenity::all::CreateThread;
+use serenity::all::EditMessage;
+use serenity::all::GetMessages;
 use serenity::all::Message;
+use serenity::all::MessageId;
+use serenity::all::MessageUpdateEvent;
+use serenity::all::Reaction;
 use serenity::all::Ready;
 use serenity::async_trait;
 use serenity::prelude::*;
+use stats::{Stats, StatsData};
 
 pub struct ConfigData;
 
@@ -25,8 +52,151 @@ impl TypeMapKey for ConfigData {
     type Value = Config;
 }
 
+pub struct StartTime;
+
+impl TypeMapKey for StartTime {
+    type Value = Instant;
+}
+
 struct Handler;
 
+async fn scan_allowed(ctx: &Context, message: &Message) -> bool {
+    let window = get_config!(ctx)
+        .scan_cooldown
+        .unwrap_or(DEFAULT_SCAN_COOLDOWN);
+    ctx.data
+        .read()
+        .await
+        .get::<CooldownData>()
+        .expect("No cooldowns?")
+        .try_start(message.id, Duration::from_secs(window))
+}
+
+/// Falls back to replying inline when the thread can't be created, e.g. without permission
+async fn reply_thread(ctx: &Context, message: &Message) -> Option<ChannelId> {
+    let reply_in_thread = get_config!(ctx).reply_in_thread;
+    if !reply_in_thread || message.guild_id.is_none() {
+        return None;
+    }
+    // Creating a second thread from the same message fails, e.g. when an edit is rescanned
+    if let Some(thread) = &message.thread {
+        return Some(thread.id);
+    }
+
+    let thread = CreateThread::new(format!("Logs from {}", message.author.name))
+        .auto_archive_duration(AutoArchiveDuration::OneDay);
+    match message
+        .channel_id
+        .create_thread_from_message(ctx, message.id, thread)
+        .await
+    {
+        Ok(thread) => Some(thread.id),
+        Err(err) => {
+            println!("Error creating log thread, replying inline: {err}");
+            None
+        }
+    }
+}
+
+async fn post_logs(ctx: &Context, message: &Message, edit: LogResponse) {
+    let reply = CreateMessage::default()
+        .content(edit.0)
+        .embeds(edit.1)
+        .components(edit.2);
+    let sent = match reply_thread(ctx, message).await {
+        Some(thread) => thread.send_message(ctx, reply).await,
+        None => {
+            message
+                .channel_id
+                .send_message(ctx, reply.reference_message(message))
+                .await
+        }
+    };
+    if let Err(err) = sent {
+        println!("Error posting log upload: {err}");
+    }
+}
+
+/// Link previews also count as edits but don't change the content, and bots are skipped
+/// so editing our own replies can't cause another scan
+fn edit_needs_rescan(event: &MessageUpdateEvent) -> bool {
+    (event.content.is_some() || event.attachments.is_some())
+        && event.author.as_ref().map_or(true, |author| !author.bot)
+}
+
+/// Remembers which logs the message has, returning whether any are new since it was last scanned
+async fn record_logs(ctx: &Context, message: &Message) -> bool {
+    let sources = log_sources(message, &get_config!(ctx).paste_services);
+    ctx.data
+        .read()
+        .await
+        .get::<ScannedLogsData>()
+        .expect("No scanned logs?")
+        .record(message.id, sources)
+}
+
+/// The bot's earlier reply to a message, if it's still among the messages right after it
+async fn previous_reply(ctx: &Context, message: &Message) -> Option<Message> {
+    let current_user = ctx.cache.current_user().id;
+    // Replies in a thread started from the message don't reference it
+    if let Some(thread) = &message.thread {
+        return thread
+            .id
+            .messages(ctx, GetMessages::new().limit(50))
+            .await
+            .ok()?
+            .into_iter()
+            .find(|reply| reply.author.id == current_user);
+    }
+
+    message
+        .channel_id
+        .messages(ctx, GetMessages::new().after(message.id).limit(50))
+        .await
+        .ok()?
+        .into_iter()
+        .find(|reply| {
+            reply.author.id == current_user
+                && reply
+                    .message_reference
+                    .as_ref()
+                    .is_some_and(|reference| reference.message_id == Some(message.id))
+        })
+}
+
+/// The message a reply was about, replies in a thread started from it share its id instead
+async fn source_message(ctx: &Context, reply: &mut Message) -> Option<Message> {
+    if let Some(source) = reply.referenced_message.take() {
+        return Some(*source);
+    }
+
+    let thread = reply.channel_id.to_channel(ctx).await.ok()?.guild()?;
+    if thread.thread_metadata.is_none() {
+        return None;
+    }
+    thread
+        .parent_id?
+        .message(ctx, MessageId::new(thread.id.get()))
+        .await
+        .ok()
+}
+
+/// Moderators can rescan any logs, others only their own so they can't keep re-uploading them
+fn may_rescan(ctx: &Context, reaction: &Reaction, source: &Message) -> bool {
+    if reaction.user_id == Some(source.author.id) {
+        return true;
+    }
+    let (Some(guild), Some(member)) = (reaction.guild_id, &reaction.member) else {
+        return false;
+    };
+    ctx.cache.guild(guild).is_some_and(|guild| {
+        guild
+            .channels
+            .get(&source.channel_id)
+            .is_some_and(|channel| guild.user_permissions_in(channel, member).manage_messages())
+    })
+}
+
 #[async_trait]
 impl EventHandler for Handler {
     async fn ready(&self, _ctx: Context, event: Ready) {
@@ -34,17 +204,17 @@ impl EventHandler for Handler {
     }
 
     async fn message(&self, ctx: Context, message: Message) {
-        match check_for_logs(&ctx, &message, false).await {
-            Ok(Some(edit)) => {
-                let reply = CreateMessage::default()
-                    .content(edit.0)
-                    .embeds(edit.1)
-                    .components(edit.2)
-                    .reference_message(&message);
-                if let Err(err) = message.channel_id.send_message(ctx, reply).await {
-                    println!("Error posting log upload: {err}");
-                }
-            }
+        if !get_config!(ctx, message.guild_id).should_scan(message.channel_id) {
+            return;
+        }
+        // Also remembered without logs, so an edit adding one is scanned
+        record_logs(&ctx, &message).await;
+        if !scan_allowed(&ctx, &message).await {
+            return;
+        }
+
+        match check_for_logs(&ctx, &message, message.author.id, false).await {
+            Ok(Some(edit)) => post_logs(&ctx, &message, edit).await,
             Ok(None) => {
                 // no-op
             }
@@ -53,17 +223,130 @@ impl EventHandler for Handler {
             }
         }
     }
+
+    async fn message_update(
+        &self,
+        ctx: Context,
+        _old_if_available: Option<Message>,
+        _new: Option<Message>,
+        event: MessageUpdateEvent,
+    ) {
+        // Checked before fetching the message, so edits in other channels don't cost a request
+        if !edit_needs_rescan(&event)
+            || !get_config!(ctx, event.guild_id).should_scan(event.channel_id)
+        {
+            return;
+        }
+        let Ok(message) = event.channel_id.message(&ctx, event.id).await else {
+            return;
+        };
+        if message.author.bot
+            || !record_logs(&ctx, &message).await
+            || !scan_allowed(&ctx, &message).await
+        {
+            return;
+        }
+
+        match check_for_logs(&ctx, &message, message.author.id, false).await {
+            // Refreshes the earlier reply instead of posting another one
+            Ok(Some(edit)) => match previous_reply(&ctx, &message).await {
+                Some(mut reply) => {
+                    let edit = EditMessage::new()
+                        .content(edit.0)
+                        .embeds(edit.1)
+                        .components(edit.2);
+                    if let Err(err) = reply.edit(&ctx, edit).await {
+                        println!("Error editing rescanned logs: {err}");
+                    }
+                }
+                None => post_logs(&ctx, &message, edit).await,
+            },
+            Ok(None) => {
+                // no-op
+            }
+            Err(err) => {
+                println!("Log rescanning threw error: {err}");
+            }
+        }
+    }
+
+    async fn reaction_add(&self, ctx: Context, reaction: Reaction) {
+        if !reaction.emoji.unicode_eq(RESCAN_EMOJI) {
+            return;
+        }
+
+        // Only the bot's replies to a log message can be rescanned
+        let Ok(mut reply) = reaction.message(&ctx).await else {
+            return;
+        };
+        if reply.author.id != ctx.cache.current_user().id {
+            return;
+        }
+        let Some(source) = source_message(&ctx, &mut reply).await else {
+            return;
+        };
+
+        let Some(requester) = reaction.user_id else {
+            return;
+        };
+        if !may_rescan(&ctx, &reaction, &source)
+            || !get_config!(ctx, reaction.guild_id).should_scan(source.channel_id)
+            || !scan_allowed(&ctx, &source).await
+        {
+            return;
+        }
+        record_logs(&ctx, &source).await;
+
+        let edit = match check_for_logs(&ctx, &source, requester, false).await {
+            Ok(Some(edit)) => EditMessage::new()
+                .content(edit.0)
+                .embeds(edit.1)
+                .components(edit.2),
+            Ok(None) => EditMessage::new()
+                .content("The original message no longer contains any logs.")
+                .embeds(vec![])
+                .components(vec![]),
+            Err(err) => {
+                println!("Log rescanning threw error: {err}");
+                return;
+            }
+        };
+        if let Err(err) = reply.edit(&ctx, edit).await {
+            println!("Error editing rescanned logs: {err}");
+        }
+        // Lets the reaction be used again, fails silently without permission to manage messages
+        let _ = reaction.delete(&ctx).await;
+    }
 }
 
 #[tokio::main]
 async fn main() {
+    // Runs the bot without arguments, `analyze <path>` only checks a local log
+    let args: Vec<String> = std::env::args().collect();
+    if let [_, command, path] = &args[..]
+        && command == "analyze"
+    {
+        cli::analyze(path);
+        return;
+    }
+
     let mut commands = vec![
         commands::general::register(),
         commands::quote::quote(),
         commands::quote::context_quote(),
+        commands::quote::show_quote(),
+        commands::quote::random_quote(),
+        commands::quote::search_quotes(),
+        commands::quote::delete_quote(),
         commands::version::version(),
         commands::check_logs::check_logs(),
         commands::modversion::modversion(),
+        commands::scan::scan(),
+        commands::checks::checks(),
+        commands::stats::stats(),
+        commands::false_positive::false_positive(),
+        commands::log_extensions::add_log_extension(),
+        commands::log_extensions::remove_log_extension(),
     ];
     commands.append(&mut commands::tags::load_tag_commands());
 
@@ -77,9 +360,27 @@ async fn main() {
         ..Default::default()
     };
 
-    let config: Config =
-        toml::from_str(&fs::read_to_string("config.toml").expect("reading config"))
-            .expect("parsing config");
+    let mut config = match load_config(CONFIG_PATH) {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("{err}\n\nA minimal config looks like this:\n\n{EXAMPLE_CONFIG}");
+            process::exit(1);
+        }
+    };
+    config.guilds = match load_guild_overrides(GUILD_CONFIG_PATH) {
+        Ok(guilds) => guilds,
+        Err(err) => {
+            eprintln!("Couldn't read the guild config at `{GUILD_CONFIG_PATH}`: {err}");
+            process::exit(1);
+        }
+    };
+    config.dynamic_checks = match load_dynamic_checks(DYNAMIC_CHECKS_PATH) {
+        Ok(checks) => checks.into(),
+        Err(err) => {
+            eprintln!("Couldn't load the checks at `{DYNAMIC_CHECKS_PATH}`: {err:#}");
+            process::exit(1);
+        }
+    };
 
     let framework = poise::Framework::builder()
         .setup(move |ctx, _ready, framework| {
@@ -99,9 +400,34 @@ async fn main() {
         .framework(framework)
         .await
         .expect("Error creating client");
+    let start = Instant::now();
+    let stats = Arc::new(Stats::new(&config.dynamic_checks));
+    let quotes = QuoteStore::open(
+        config
+            .quotes_db
+            .clone()
+            .unwrap_or_else(|| DEFAULT_QUOTES_DB.to_string()),
+    )
+    .expect("opening quotes database");
+
+    if let Some(port) = config.health_port {
+        let stats = stats.clone();
+        tokio::spawn(async move {
+            if let Err(err) = health::serve(port, stats, start).await {
+                println!("Health endpoint stopped: {err}");
+            }
+        });
+    }
+
     {
         let mut data_lock = client.data.write().await;
         data_lock.insert::<ConfigData>(config);
+        data_lock.insert::<StartTime>(start);
+        data_lock.insert::<StatsData>(stats);
+        data_lock.insert::<QuotesData>(Arc::new(quotes));
+        data_lock.insert::<CooldownData>(Cooldowns::default());
+        data_lock.insert::<UploadLimitsData>(UploadLimits::default());
+        data_lock.insert::<ScannedLogsData>(ScannedLogs::default());
     }
 
     // start listening for events by starting a single shard
@@ -109,3 +435,43 @@ async fn main() {
         println!("An error occurred while running the client: {why:?}");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edit(fields: serde_json::Value) -> MessageUpdateEvent {
+        let mut event = serde_json::json!({ "id": "1", "channel_id": "2" });
+        event
+            .as_object_mut()
+            .unwrap()
+            .extend(fields.as_object().unwrap().clone());
+        serde_json::from_value(event).unwrap()
+    }
+
+    #[test]
+    fn edits_are_rescanned_unless_from_bots_or_link_previews() {
+        let user = |bot| {
+            serde_json::json!({
+                "id": "3",
+                "username": "steve",
+                "discriminator": "0",
+                "global_name": null,
+                "avatar": null,
+                "bot": bot,
+            })
+        };
+        assert!(edit_needs_rescan(&edit(
+            serde_json::json!({ "content": "https://mclo.gs/abc", "author": user(false) })
+        )));
+        assert!(edit_needs_rescan(&edit(
+            serde_json::json!({ "attachments": [] })
+        )));
+        assert!(!edit_needs_rescan(&edit(
+            serde_json::json!({ "embeds": [] })
+        )));
+        assert!(!edit_needs_rescan(&edit(
+            serde_json::json!({ "content": "Uploaded latest.log", "author": user(true) })
+        )));
+    }
+}
@@ -0,0 +1,239 @@
+use std::{
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serenity::{all::GuildId, client::Context, prelude::TypeMapKey};
+
+pub(crate) const DEFAULT_QUOTES_DB: &str = "quotes.db";
+
+pub(crate) struct Quote {
+    pub(crate) id: i64,
+    pub(crate) author: String,
+    pub(crate) content: String,
+    pub(crate) image: Option<String>,
+}
+
+impl Quote {
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: row.get("id")?,
+            author: row.get("author")?,
+            content: row.get("content")?,
+            image: row.get("image")?,
+        })
+    }
+}
+
+/// Quotes are kept per guild, so one guild can't read or delete another's
+pub(crate) struct QuoteStore {
+    connection: Mutex<Connection>,
+}
+
+impl QuoteStore {
+    pub(crate) fn open(path: impl AsRef<Path>) -> rusqlite::Result<Self> {
+        let connection = Connection::open(path)?;
+        connection.execute(
+            "CREATE TABLE IF NOT EXISTS quotes (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                guild INTEGER NOT NULL,
+                author TEXT NOT NULL,
+                content TEXT NOT NULL,
+                image TEXT,
+                created_at INTEGER NOT NULL DEFAULT (unixepoch())
+            )",
+            (),
+        )?;
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    fn connection(&self) -> std::sync::MutexGuard<'_, Connection> {
+        self.connection.lock().expect("Quote store poisoned")
+    }
+
+    pub(crate) fn add(
+        &self,
+        guild: GuildId,
+        author: &str,
+        content: &str,
+        image: Option<&str>,
+    ) -> rusqlite::Result<i64> {
+        let connection = self.connection();
+        connection.execute(
+            "INSERT INTO quotes (guild, author, content, image) VALUES (?1, ?2, ?3, ?4)",
+            params![guild.get() as i64, author, content, image],
+        )?;
+        Ok(connection.last_insert_rowid())
+    }
+
+    pub(crate) fn get(&self, guild: GuildId, id: i64) -> rusqlite::Result<Option<Quote>> {
+        self.connection()
+            .query_row(
+                "SELECT id, author, content, image FROM quotes WHERE guild = ?1 AND id = ?2",
+                params![guild.get() as i64, id],
+                Quote::from_row,
+            )
+            .optional()
+    }
+
+    pub(crate) fn random(&self, guild: GuildId) -> rusqlite::Result<Option<Quote>> {
+        self.connection()
+            .query_row(
+                "SELECT id, author, content, image FROM quotes WHERE guild = ?1 ORDER BY RANDOM() LIMIT 1",
+                params![guild.get() as i64],
+                Quote::from_row,
+            )
+            .optional()
+    }
+
+    /// Case-insensitive search in the content and author, returns a page of quotes and the total matches
+    pub(crate) fn search(
+        &self,
+        guild: GuildId,
+        text: &str,
+        limit: u64,
+        offset: u64,
+    ) -> rusqlite::Result<(Vec<Quote>, u64)> {
+        const MATCHES: &str = "guild = ?1 AND (instr(lower(content), lower(?2)) > 0 OR instr(lower(author), lower(?2)) > 0)";
+        let connection = self.connection();
+
+        let total = connection.query_row(
+            &format!("SELECT COUNT(*) FROM quotes WHERE {MATCHES}"),
+            params![guild.get() as i64, text],
+            |row| row.get(0),
+        )?;
+        let quotes = connection
+            .prepare(&format!(
+                "SELECT id, author, content, image FROM quotes WHERE {MATCHES} ORDER BY id LIMIT ?3 OFFSET ?4"
+            ))?
+            .query_map(
+                params![guild.get() as i64, text, limit as i64, offset as i64],
+                Quote::from_row,
+            )?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok((quotes, total))
+    }
+
+    /// Returns whether the quote existed
+    pub(crate) fn delete(&self, guild: GuildId, id: i64) -> rusqlite::Result<bool> {
+        let deleted = self.connection().execute(
+            "DELETE FROM quotes WHERE guild = ?1 AND id = ?2",
+            params![guild.get() as i64, id],
+        )?;
+        Ok(deleted > 0)
+    }
+}
+
+pub(crate) struct QuotesData;
+
+impl TypeMapKey for QuotesData {
+    type Value = Arc<QuoteStore>;
+}
+
+pub(crate) async fn get_quotes(ctx: &Context) -> Arc<QuoteStore> {
+    ctx.data
+        .read()
+        .await
+        .get::<QuotesData>()
+        .expect("No quotes?")
+        .clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GUILD: GuildId = GuildId::new(1);
+    const OTHER_GUILD: GuildId = GuildId::new(2);
+
+    #[test]
+    fn add_get_and_delete() {
+        let store = QuoteStore::open(":memory:").unwrap();
+        let id = store.add(GUILD, "Steve", "I love diamonds", None).unwrap();
+
+        let quote = store.get(GUILD, id).unwrap().expect("No quote");
+        assert_eq!(quote.author, "Steve");
+        assert_eq!(quote.content, "I love diamonds");
+        assert!(quote.image.is_none());
+
+        assert!(store.delete(GUILD, id).unwrap());
+        assert!(!store.delete(GUILD, id).unwrap());
+        assert!(store.get(GUILD, id).unwrap().is_none());
+    }
+
+    #[test]
+    fn quotes_are_per_guild() {
+        let store = QuoteStore::open(":memory:").unwrap();
+        let id = store.add(GUILD, "Steve", "I love diamonds", None).unwrap();
+        assert!(store.get(OTHER_GUILD, id).unwrap().is_none());
+        assert!(!store.delete(OTHER_GUILD, id).unwrap());
+    }
+
+    #[test]
+    fn random_quote_is_from_the_guild() {
+        let store = QuoteStore::open(":memory:").unwrap();
+        assert!(store.random(GUILD).unwrap().is_none());
+        let id = store
+            .add(
+                GUILD,
+                "Alex",
+                "Creepers",
+                Some("https://example.com/creeper.png"),
+            )
+            .unwrap();
+
+        let quote = store.random(GUILD).unwrap().expect("No quote");
+        assert_eq!(quote.id, id);
+        assert_eq!(
+            quote.image.as_deref(),
+            Some("https://example.com/creeper.png")
+        );
+        assert!(store.random(OTHER_GUILD).unwrap().is_none());
+    }
+
+    #[test]
+    fn search_matches_content_and_author() {
+        let store = QuoteStore::open(":memory:").unwrap();
+        store.add(GUILD, "Steve", "I love Diamonds", None).unwrap();
+        store
+            .add(GUILD, "Diamond Dan", "Mining time", None)
+            .unwrap();
+        store.add(GUILD, "Alex", "Creepers", None).unwrap();
+        store
+            .add(OTHER_GUILD, "Steve", "Diamonds elsewhere", None)
+            .unwrap();
+
+        let (quotes, total) = store.search(GUILD, "diamond", 10, 0).unwrap();
+        assert_eq!(total, 2);
+        assert_eq!(quotes.len(), 2);
+
+        let (quotes, total) = store.search(GUILD, "emerald", 10, 0).unwrap();
+        assert_eq!(total, 0);
+        assert!(quotes.is_empty());
+    }
+
+    #[test]
+    fn search_pages() {
+        let store = QuoteStore::open(":memory:").unwrap();
+        for i in 0..5 {
+            store
+                .add(GUILD, "Steve", &format!("Quote {i}"), None)
+                .unwrap();
+        }
+
+        let (first, total) = store.search(GUILD, "quote", 2, 0).unwrap();
+        assert_eq!(total, 5);
+        assert_eq!(first.len(), 2);
+        assert_eq!(first[0].content, "Quote 0");
+
+        let (last, _) = store.search(GUILD, "quote", 2, 4).unwrap();
+        assert_eq!(last.len(), 1);
+        assert_eq!(last[0].content, "Quote 4");
+
+        let (past_end, _) = store.search(GUILD, "quote", 2, 6).unwrap();
+        assert!(past_end.is_empty());
+    }
+}
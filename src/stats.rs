@@ -0,0 +1,51 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use serenity::{client::Context, prelude::TypeMapKey};
+
+use crate::log_checking::{checks::registered_checks, dynamic::DynamicCheck};
+
+pub(crate) struct Stats {
+    pub(crate) logs_scanned: AtomicU64,
+    pub(crate) upload_failures: AtomicU64,
+    /// Every check has a counter from the start, so no lock is needed to update them
+    pub(crate) checks_triggered: HashMap<&'static str, AtomicU64>,
+}
+
+impl Stats {
+    pub(crate) fn new(dynamic_checks: &[DynamicCheck]) -> Self {
+        Self {
+            logs_scanned: AtomicU64::new(0),
+            upload_failures: AtomicU64::new(0),
+            checks_triggered: registered_checks(dynamic_checks)
+                .map(|check| (check.id, AtomicU64::new(0)))
+                .collect(),
+        }
+    }
+
+    pub(crate) fn record_check(&self, id: &str) {
+        if let Some(counter) = self.checks_triggered.get(id) {
+            counter.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+pub(crate) struct StatsData;
+
+impl TypeMapKey for StatsData {
+    type Value = Arc<Stats>;
+}
+
+pub(crate) async fn get_stats(ctx: &Context) -> Arc<Stats> {
+    ctx.data
+        .read()
+        .await
+        .get::<StatsData>()
+        .expect("No stats?")
+        .clone()
+}
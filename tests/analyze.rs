@@ -0,0 +1,25 @@
+use mental_instability_bot::log_checking::{analyze_log, check_checks_json, checks::Severity};
+
+const MISSING_DEPENDENCY: &str = include_str!("logs/missing_dependency.log");
+
+#[test]
+fn analyze_sample_log() {
+    let reports = analyze_log(MISSING_DEPENDENCY);
+    let report = reports
+        .iter()
+        .find(|report| report.title == "Missing dependency")
+        .expect("No missing dependency report");
+    assert_eq!(report.severity, Severity::High);
+    assert!(report.description.contains("`Mod Menu`"));
+    assert!(report.description.contains("`fabric-api`"));
+}
+
+#[test]
+fn json_reports_have_ids() {
+    let reports = check_checks_json(MISSING_DEPENDENCY);
+    assert!(reports
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|report| report["id"] == "dependency_generic" && report["severity"] == "High"));
+}